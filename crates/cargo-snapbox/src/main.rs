@@ -0,0 +1,373 @@
+//! Review, accept, and prune [snapbox](https://docs.rs/snapbox) snapshots
+//!
+//! Run `cargo snapbox help` for usage.
+
+#![warn(clippy::print_stderr)]
+#![warn(clippy::print_stdout)]
+
+mod orphans;
+
+use std::ffi::OsString;
+use std::io::BufRead as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use snapbox::data::PendingSnapshot;
+use snapbox::report::Palette;
+
+const USAGE: &str = "\
+Review, accept, and prune snapbox snapshots
+
+Usage: cargo snapbox <COMMAND> [PATH] [-- <CARGO_TEST_ARGS>...]
+
+Commands:
+  pending  List pending snapshot updates
+  review   Show each pending update and accept or reject it
+  accept   Accept all pending updates
+  reject   Reject all pending updates
+  orphans  List snapshot files under `tests/snapshots/` that no `file![_]` refers to
+  prune    Run `cargo test` and list snapshot files under `tests/snapshots/` that it did not use
+  help     Print this message
+
+Arguments:
+  [PATH]                Directory to search [default: .]
+  [CARGO_TEST_ARGS]...  With `prune`, arguments for `cargo test`
+
+Options:
+  --delete  With `orphans` or `prune`, remove the files found
+
+Record pending updates by running tests with `SNAPSHOTS=review`.
+";
+
+fn main() -> ExitCode {
+    let result = Args::parse(std::env::args_os()).and_then(run);
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let palette = Palette::color();
+            let _ = writeln!(anstream::stderr(), "{}: {err}", palette.error("error"));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Pending,
+    Review,
+    Accept,
+    Reject,
+    Orphans { delete: bool },
+    Prune { delete: bool },
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    command: Command,
+    path: PathBuf,
+    cargo_args: Vec<OsString>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = OsString>) -> Result<Self, String> {
+        let mut args = args.into_iter().skip(1).peekable();
+        // `cargo snapbox` passes the subcommand name along
+        let _ = args.next_if(|arg| arg == "snapbox");
+
+        let mut command = None;
+        let mut path = None;
+        let mut delete = false;
+        let mut cargo_args = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg.to_str() {
+                Some("--") => {
+                    cargo_args.extend(args.by_ref());
+                    break;
+                }
+                Some("-h" | "--help") => {
+                    command = Some(Command::Help);
+                    break;
+                }
+                Some("--delete") => delete = true,
+                Some(flag) if flag.starts_with('-') => {
+                    return Err(format!("unexpected argument `{flag}`"));
+                }
+                Some(name) if command.is_none() => {
+                    command = Some(match name {
+                        "pending" => Command::Pending,
+                        "review" => Command::Review,
+                        "accept" => Command::Accept,
+                        "reject" => Command::Reject,
+                        "orphans" => Command::Orphans { delete: false },
+                        "prune" => Command::Prune { delete: false },
+                        "help" => Command::Help,
+                        _ => return Err(format!("unknown command `{name}`\n\n{USAGE}")),
+                    });
+                }
+                _ if command.is_some() && path.is_none() => path = Some(PathBuf::from(arg)),
+                _ => {
+                    return Err(format!("unexpected argument `{}`", arg.to_string_lossy()));
+                }
+            }
+        }
+
+        let mut command = command.ok_or_else(|| format!("no command given\n\n{USAGE}"))?;
+        if delete {
+            match &mut command {
+                Command::Orphans { delete } | Command::Prune { delete } => *delete = true,
+                Command::Help => {}
+                _ => {
+                    return Err("`--delete` is only supported by `orphans` and `prune`".to_owned());
+                }
+            }
+        }
+        if !cargo_args.is_empty() && !matches!(command, Command::Prune { .. } | Command::Help) {
+            return Err("`cargo test` arguments are only supported by `prune`".to_owned());
+        }
+        Ok(Self {
+            command,
+            path: path.unwrap_or_else(|| PathBuf::from(".")),
+            cargo_args,
+        })
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let mut stdout = anstream::stdout();
+    match args.command {
+        Command::Pending => {
+            let pending = find_pending(&args)?;
+            for update in &pending {
+                let _ = writeln!(stdout, "{update}");
+            }
+        }
+        Command::Review => {
+            let pending = find_pending(&args)?;
+            review(pending)?;
+        }
+        Command::Accept => {
+            let pending = find_pending(&args)?;
+            let count = pending.len();
+            PendingSnapshot::accept_all(pending).map_err(|e| e.to_string())?;
+            let _ = writeln!(stdout, "Accepted {count} snapshot update(s)");
+        }
+        Command::Reject => {
+            let pending = find_pending(&args)?;
+            let count = pending.len();
+            PendingSnapshot::reject_all(pending).map_err(|e| e.to_string())?;
+            let _ = writeln!(stdout, "Rejected {count} snapshot update(s)");
+        }
+        Command::Orphans { delete } => {
+            let orphans = orphans::find(&args.path)?;
+            report_unused(orphans, delete)?;
+        }
+        Command::Prune { delete } => {
+            let unused = prune(&args)?;
+            report_unused(unused, delete)?;
+        }
+        Command::Help => {
+            let _ = write!(stdout, "{USAGE}");
+        }
+    }
+    Ok(())
+}
+
+fn report_unused(paths: Vec<PathBuf>, delete: bool) -> Result<(), String> {
+    let mut stdout = anstream::stdout();
+    for path in paths {
+        if delete {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("failed to remove {}: {e}", path.display()))?;
+            let _ = writeln!(stdout, "Removed {}", path.display());
+        } else {
+            let _ = writeln!(stdout, "{}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Run `cargo test`, recording the snapshots used, and return those that weren't
+fn prune(args: &Args) -> Result<Vec<PathBuf>, String> {
+    let packages = orphans::find_packages(&args.path)?
+        .into_iter()
+        .filter(|package| package.join(orphans::SNAPSHOT_DIR).is_dir())
+        .collect::<Vec<_>>();
+    for package in &packages {
+        snapbox::data::clear_used_snapshots(package).map_err(|e| e.to_string())?;
+    }
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let status = std::process::Command::new(cargo)
+        .arg("test")
+        .args(&args.cargo_args)
+        .current_dir(&args.path)
+        .env(snapbox::assert::DEFAULT_ACTION_ENV, "prune")
+        .status()
+        .map_err(|e| format!("failed to run `cargo test`: {e}"))?;
+
+    let mut unused = Vec::new();
+    if status.success() {
+        for package in &packages {
+            match snapbox::data::unused_snapshots(package) {
+                Ok(package_unused) => unused.extend(package_unused),
+                Err(err) => {
+                    let _ = writeln!(anstream::stderr(), "Skipping {}: {err}", package.display());
+                }
+            }
+        }
+    }
+    for package in &packages {
+        snapbox::data::clear_used_snapshots(package).map_err(|e| e.to_string())?;
+    }
+    if !status.success() {
+        return Err(
+            "`cargo test` failed, snapshots of tests that did not pass would be reported as unused"
+                .to_owned(),
+        );
+    }
+    Ok(unused)
+}
+
+fn find_pending(args: &Args) -> Result<Vec<PendingSnapshot>, String> {
+    let pending = PendingSnapshot::find(&args.path).map_err(|e| e.to_string())?;
+    if pending.is_empty() {
+        let _ = writeln!(anstream::stderr(), "No pending snapshot updates");
+    }
+    Ok(pending)
+}
+
+fn review(pending: Vec<PendingSnapshot>) -> Result<(), String> {
+    let palette = Palette::color();
+    let mut stdout = anstream::stdout();
+    let mut stdin = std::io::stdin().lock();
+
+    let total = pending.len();
+    let mut accepted = Vec::new();
+    let mut rejected = Vec::new();
+    for (i, update) in pending.into_iter().enumerate() {
+        let expected = update.expected().map_err(|e| e.to_string())?;
+        let actual = update.actual().map_err(|e| e.to_string())?;
+        let mut diff = String::new();
+        snapbox::report::write_diff(
+            &mut diff,
+            &expected,
+            &actual,
+            Some(&update),
+            Some(&update.pending_path().display()),
+            palette,
+        )
+        .map_err(|e| e.to_string())?;
+        let _ = write!(stdout, "{diff}");
+
+        let decision = loop {
+            let _ = write!(
+                stdout,
+                "[{}/{total}] Accept {}? [y]es, [n]o, [s]kip, [q]uit: ",
+                i + 1,
+                palette.info(&update)
+            );
+            let _ = stdout.flush();
+            let mut line = String::new();
+            let read = stdin.read_line(&mut line).map_err(|e| e.to_string())?;
+            if read == 0 {
+                break Decision::Quit;
+            }
+            match line.trim() {
+                "y" | "yes" => break Decision::Accept,
+                "n" | "no" => break Decision::Reject,
+                "s" | "skip" => break Decision::Skip,
+                "q" | "quit" => break Decision::Quit,
+                _ => {}
+            }
+        };
+        match decision {
+            Decision::Accept => accepted.push(update),
+            Decision::Reject => rejected.push(update),
+            Decision::Skip => {}
+            Decision::Quit => break,
+        }
+    }
+
+    let accepted_count = accepted.len();
+    let rejected_count = rejected.len();
+    PendingSnapshot::accept_all(accepted).map_err(|e| e.to_string())?;
+    PendingSnapshot::reject_all(rejected).map_err(|e| e.to_string())?;
+    let _ = writeln!(
+        stdout,
+        "Accepted {accepted_count}, rejected {rejected_count} of {total} snapshot update(s)"
+    );
+    Ok(())
+}
+
+enum Decision {
+    Accept,
+    Reject,
+    Skip,
+    Quit,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(OsString::from))
+    }
+
+    #[test]
+    fn parse_cargo_subcommand() {
+        let args = parse(&["cargo-snapbox", "snapbox", "review", "tests"]).unwrap();
+        assert_eq!(
+            args,
+            Args {
+                command: Command::Review,
+                path: PathBuf::from("tests"),
+                cargo_args: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_default_path() {
+        let args = parse(&["cargo-snapbox", "orphans", "--delete"]).unwrap();
+        assert_eq!(
+            args,
+            Args {
+                command: Command::Orphans { delete: true },
+                path: PathBuf::from("."),
+                cargo_args: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_cargo_test_args() {
+        let args = parse(&["cargo-snapbox", "prune", "--", "--workspace", "--delete"]).unwrap();
+        assert_eq!(
+            args,
+            Args {
+                command: Command::Prune { delete: false },
+                path: PathBuf::from("."),
+                cargo_args: vec![OsString::from("--workspace"), OsString::from("--delete")],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cases: &[&[&str]] = &[
+            &["cargo-snapbox"],
+            &["cargo-snapbox", "snapbox"],
+            &["cargo-snapbox", "unknown"],
+            &["cargo-snapbox", "accept", "--delete"],
+            &["cargo-snapbox", "accept", "--force"],
+            &["cargo-snapbox", "accept", "a", "b"],
+            &["cargo-snapbox", "accept", "--", "--workspace"],
+        ];
+        for args in cases {
+            assert!(parse(args).is_err(), "{args:?}");
+        }
+    }
+}
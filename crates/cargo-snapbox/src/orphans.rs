@@ -0,0 +1,179 @@
+//! Find snapshot files that are no longer referenced
+//!
+//! Snapshot paths generated by `file![_]` depend on the module path of the test, so this
+//! approximates it from the Rust source: a snapshot is referenced when its file name appears in a
+//! source file, or when its function name is defined in a source file that uses `file![_`.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+pub(crate) const SNAPSHOT_DIR: &str = "tests/snapshots";
+const GENERATED_SNAPSHOT: &str = "file![_";
+
+/// Snapshot files in each package's `tests/snapshots/` under `root` that no source refers to
+///
+/// Pending updates are not snapshots, so are left for `review`.
+pub(crate) fn find(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut orphans = Vec::new();
+    for package in find_packages(root)? {
+        let snapshot_dir = package.join(SNAPSHOT_DIR);
+        if !snapshot_dir.is_dir() {
+            continue;
+        }
+        let sources = read_sources(&package)?;
+        for snapshot in list_files(&snapshot_dir, |path| !is_pending(path))? {
+            if !is_referenced(&snapshot, &sources) {
+                orphans.push(snapshot);
+            }
+        }
+    }
+    orphans.sort();
+    Ok(orphans)
+}
+
+pub(crate) fn find_packages(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let manifests = list_files(root, |path| {
+        path.file_name().is_some_and(|name| name == "Cargo.toml")
+    })?;
+    Ok(manifests
+        .into_iter()
+        .filter_map(|manifest| manifest.parent().map(|p| p.to_owned()))
+        .collect())
+}
+
+fn read_sources(package: &Path) -> Result<Vec<String>, String> {
+    let sources = list_files(package, |path| {
+        path.extension().is_some_and(|ext| ext == "rs")
+    })?;
+    sources
+        .iter()
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))
+        })
+        .collect()
+}
+
+fn list_files(root: &Path, include: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for entry in ignore::WalkBuilder::new(root).build() {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let is_file = entry.file_type().is_some_and(|t| t.is_file());
+        if is_file && include(entry.path()) {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+fn is_pending(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(snapbox::data::PENDING_SUFFIX))
+}
+
+fn is_referenced(snapshot: &Path, sources: &[String]) -> bool {
+    let Some(file_name) = snapshot.file_name().and_then(|name| name.to_str()) else {
+        return true;
+    };
+    if sources.iter().any(|source| source.contains(file_name)) {
+        return true;
+    }
+
+    let Some(fn_name) = generated_fn_name(file_name) else {
+        return false;
+    };
+    sources
+        .iter()
+        .any(|source| source.contains(GENERATED_SNAPSHOT) && defines_fn(source, fn_name))
+}
+
+/// Recover the function name from `{module}__{fn}[@{count}].{ext}`
+fn generated_fn_name(file_name: &str) -> Option<&str> {
+    let (stem, _ext) = file_name.split_once('.')?;
+    let stem = stem
+        .split_once('@')
+        .map(|(stem, _count)| stem)
+        .unwrap_or(stem);
+    let (_module, fn_name) = stem.rsplit_once("__")?;
+    (!fn_name.is_empty()).then_some(fn_name)
+}
+
+fn defines_fn(source: &str, fn_name: &str) -> bool {
+    let needle = format!("fn {fn_name}");
+    source.match_indices(&needle).any(|(start, _)| {
+        let rest = &source[start + needle.len()..];
+        !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn skip_pending() {
+        let root = snapbox::dir::DirRoot::mutable_temp().unwrap();
+        let package = root.path().unwrap();
+        let snapshot_dir = package.join(SNAPSHOT_DIR);
+        std::fs::create_dir_all(&snapshot_dir).unwrap();
+        std::fs::write(package.join("Cargo.toml"), "").unwrap();
+        std::fs::write(snapshot_dir.join("hand.txt"), "").unwrap();
+        std::fs::write(snapshot_dir.join("hand.txt.snap.new"), "").unwrap();
+
+        let orphans = find(package).unwrap();
+        assert_eq!(orphans, vec![snapshot_dir.join("hand.txt")]);
+
+        root.close().unwrap();
+    }
+
+    #[test]
+    fn generated_names() {
+        let cases = [
+            ("testsuite__assert__basic.txt", Some("basic")),
+            ("testsuite__assert__basic@2.json", Some("basic")),
+            ("module__fn_with__underscores.txt", Some("underscores")),
+            ("hand_written.txt", None),
+        ];
+        for (file_name, expected) in cases {
+            assert_eq!(generated_fn_name(file_name), expected, "{file_name}");
+        }
+    }
+
+    #[test]
+    fn referenced() {
+        let sources = [
+            "
+#[test]
+fn basic() {
+    assert_data_eq!(actual, file![_]);
+}
+
+fn basic_helper() {}
+"
+            .to_owned(),
+            r#"
+fn explicit() {
+    assert_data_eq!(actual, file!["snapshots/hand_written.txt"]);
+}
+"#
+            .to_owned(),
+        ];
+        let cases = [
+            ("tests/snapshots/testsuite__basic.txt", true),
+            ("tests/snapshots/testsuite__basic@1.txt", true),
+            ("tests/snapshots/hand_written.txt", true),
+            ("tests/snapshots/testsuite__explicit.txt", false),
+            ("tests/snapshots/testsuite__basic_other.txt", false),
+            ("tests/snapshots/testsuite__removed.txt", false),
+            ("tests/snapshots/other.txt", false),
+        ];
+        for (snapshot, expected) in cases {
+            assert_eq!(
+                is_referenced(Path::new(snapshot), &sources),
+                expected,
+                "{snapshot}"
+            );
+        }
+    }
+}
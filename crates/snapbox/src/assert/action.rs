@@ -1,5 +1,29 @@
 pub const DEFAULT_ACTION_ENV: &str = "SNAPSHOTS";
 
+/// Value for an action environment variable to [review][crate::Assert::review] mismatches
+pub(crate) const REVIEW_ENV_VALUE: &str = "review";
+
+/// Set to allow [`Action::Overwrite`] from an environment variable when running in CI
+///
+/// See [`is_overwrite_allowed`]
+pub const ALLOW_CI_OVERWRITE_ENV: &str = "SNAPSHOTS_ALLOW_CI_OVERWRITE";
+
+/// Environment variables set by CI providers that don't set `CI`
+const CI_PROVIDER_ENVS: &[&str] = &[
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "TF_BUILD",
+    "BUILDKITE",
+    "CIRCLECI",
+    "TRAVIS",
+    "JENKINS_URL",
+    "TEAMCITY_VERSION",
+    "APPVEYOR",
+    "BITBUCKET_BUILD_NUMBER",
+    "CODEBUILD_BUILD_ID",
+    "DRONE",
+];
+
 /// Test action, see [`Assert`][crate::Assert]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub enum Action {
@@ -11,14 +35,25 @@ pub enum Action {
     #[default]
     Verify,
     /// Overwrite on mismatch
+    ///
+    /// When read from an environment variable, this is replaced with [`Action::Verify`] unless
+    /// [`is_overwrite_allowed`]
     Overwrite,
 }
 
 impl Action {
+    /// Read the action from an environment variable, refusing to overwrite in CI
+    ///
+    /// See [`is_overwrite_allowed`]
     pub fn with_env_var(var: impl AsRef<std::ffi::OsStr>) -> Option<Self> {
         let var = var.as_ref();
         let value = std::env::var_os(var)?;
-        Self::with_env_value(value)
+        let action = Self::with_env_value(value)?;
+        if action == Action::Overwrite && !is_overwrite_allowed() {
+            Some(Action::Verify)
+        } else {
+            Some(action)
+        }
     }
 
     pub fn with_env_value(value: impl AsRef<std::ffi::OsStr>) -> Option<Self> {
@@ -31,4 +66,59 @@ impl Action {
             _ => None,
         }
     }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Action::Skip => "skip",
+            Action::Ignore => "ignore",
+            Action::Verify => "verify",
+            Action::Overwrite => "overwrite",
+        }
+    }
+}
+
+/// Whether snapshots may be overwritten as requested by an environment variable
+///
+/// This is `false` when running in CI, detected through `CI` or provider-specific variables, so
+/// a leftover `SNAPSHOTS=overwrite` can't hide failures.  Set [`ALLOW_CI_OVERWRITE_ENV`] to
+/// allow it anyway.
+pub fn is_overwrite_allowed() -> bool {
+    is_overwrite_allowed_with(|var| std::env::var_os(var))
+}
+
+fn is_overwrite_allowed_with(var: impl Fn(&str) -> Option<std::ffi::OsString>) -> bool {
+    let is_set = |name: &str| {
+        var(name).is_some_and(|value| !value.is_empty() && value != "0" && value != "false")
+    };
+    let is_ci = is_set("CI") || CI_PROVIDER_ENVS.iter().any(|name| is_set(name));
+    !is_ci || is_set(ALLOW_CI_OVERWRITE_ENV)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn overwrite_allowed() {
+        let cases: &[(&[(&str, &str)], bool)] = &[
+            (&[], true),
+            (&[("CI", "true")], false),
+            (&[("CI", "1")], false),
+            (&[("CI", "false")], true),
+            (&[("CI", "0")], true),
+            (&[("CI", "")], true),
+            (&[("GITHUB_ACTIONS", "true")], false),
+            (&[("JENKINS_URL", "https://ci.example.com")], false),
+            (&[("CI", "true"), (ALLOW_CI_OVERWRITE_ENV, "1")], true),
+            (&[("CI", "true"), (ALLOW_CI_OVERWRITE_ENV, "0")], false),
+        ];
+        for (env, expected) in cases {
+            let actual = is_overwrite_allowed_with(|name| {
+                env.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.into())
+            });
+            assert_eq!(actual, *expected, "{env:?}");
+        }
+    }
 }
@@ -18,9 +18,17 @@ impl Error {
         }
     }
 
+    /// The failure, without the backtrace
+    pub(crate) fn message(&self) -> &str {
+        &self.inner
+    }
+
+    /// Report the failure, deferring it when within [`Assert::soft`][crate::Assert::soft]
     #[track_caller]
-    pub(crate) fn panic(self) -> ! {
-        panic!("{self}")
+    pub(crate) fn panic(self) {
+        if let Some(err) = super::soft::collect(self, std::panic::Location::caller()) {
+            panic!("{err}")
+        }
     }
 }
 
@@ -1,5 +1,6 @@
 mod action;
 mod error;
+mod soft;
 
 #[cfg(feature = "color")]
 use anstream::panic;
@@ -9,10 +10,16 @@ use anstream::stderr;
 use std::io::stderr;
 
 use crate::IntoData;
-use crate::filter::{Filter as _, FilterNewlines, FilterPaths, NormalizeToExpected};
+use crate::filter::{
+    Filter as _, FilterCollapseSpaces, FilterNewlines, FilterPaths, FilterRemoveBlankLines,
+    FilterStripAnsi, FilterTrimTrailingWhitespace, NormalizeToExpected,
+};
 
+pub use action::ALLOW_CI_OVERWRITE_ENV;
 pub use action::Action;
 pub use action::DEFAULT_ACTION_ENV;
+use action::REVIEW_ENV_VALUE;
+pub use action::is_overwrite_allowed;
 pub use error::Error;
 pub use error::Result;
 
@@ -34,8 +41,16 @@ pub use error::Result;
 pub struct Assert {
     pub(crate) action: Action,
     action_var: Option<String>,
+    overwrite_refused: bool,
+    review: bool,
     normalize_paths: bool,
     substitutions: crate::Redactions,
+    formats: Vec<crate::data::CustomFormat>,
+    float_tolerance: Option<f64>,
+    strip_ansi: bool,
+    report: Option<crate::report::Report>,
+    #[cfg(feature = "diff")]
+    patch_dir: Option<crate::report::PatchDir>,
     pub(crate) palette: crate::report::Palette,
 }
 
@@ -50,8 +65,14 @@ impl Assert {
     /// By default [`filters`][crate::filter] are applied, including:
     /// - `...` is a line-wildcard when on a line by itself
     /// - `[..]` is a character-wildcard when inside a line
+    /// - `[..:int]`, `[..:float]`, `[..:word]`, `[..:path]` and `[..:hex]` are character-wildcards
+    ///   limited to that kind of value
+    /// - `[~1.5]` or `[~1.5±0.01]` matches a number within a tolerance
     /// - `[EXE]` matches `.exe` on Windows
+    /// - `[NAME#1]` matches a value redacted as `[NAME]`, requiring the same value everywhere
+    ///   `[NAME#1]` is used, while `[NAME]` still matches any value
     /// - `"{...}"` is a JSON value wildcard
+    /// - `"{~1.5}"` or `"{~1.5±0.01}"` matches a JSON number within a tolerance
     /// - `"...": "{...}"` is a JSON key-value wildcard
     /// - `\` to `/`
     /// - Newlines
@@ -87,6 +108,28 @@ impl Assert {
         }
     }
 
+    /// Run every assertion within `f`, reporting all failures together once it finishes
+    ///
+    /// This covers all assertions on the current thread, including [`assert_data_eq!`] and
+    /// [`OutputAssert`][crate::cmd::OutputAssert], so one run can show, or
+    /// [review][Assert::review], every mismatched snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,should_panic
+    /// # use snapbox::Assert;
+    /// Assert::new().soft(|assert| {
+    ///     assert.eq("hello", "goodbye");
+    ///     assert.eq("world", "moon");
+    /// });
+    /// ```
+    ///
+    /// [`assert_data_eq!`]: crate::assert_data_eq
+    #[track_caller]
+    pub fn soft<R>(&self, f: impl FnOnce(&Self) -> R) -> R {
+        soft::run(|| f(self))
+    }
+
     #[track_caller]
     #[deprecated(since = "0.6.0", note = "Replaced with `Assert::eq`")]
     pub fn eq_(&self, actual: impl IntoData, expected: impl IntoData) {
@@ -109,9 +152,32 @@ impl Assert {
             Action::Ignore | Action::Verify | Action::Overwrite => {}
         }
 
+        let start = std::time::Instant::now();
         let (actual, expected) = self.normalize(actual, expected);
 
-        self.do_action(actual_name, actual, expected)
+        let verified = self.try_verify(actual_name, &actual, &expected);
+
+        let Some(report) = self.report.as_ref() else {
+            return self.do_action(verified, actual, expected);
+        };
+        let mut entry =
+            crate::report::ReportEntry::new(report_name(actual_name, &expected), verified.is_ok());
+        if let Some(source) = expected.source() {
+            entry = entry.expected(source);
+        }
+        if let Err(err) = &verified {
+            entry = entry
+                .diff(crate::filter::strip_ansi(err.message()))
+                .action(self.action_name());
+        }
+        let result = self.do_action(verified, actual, expected);
+        entry = entry.duration(start.elapsed());
+        if let Err(err) = report.write(&entry) {
+            use std::io::Write;
+
+            let _ = writeln!(stderr(), "{}: {}", self.palette.warn("Report failed"), err);
+        }
+        result
     }
 
     pub fn normalize(
@@ -119,20 +185,42 @@ impl Assert {
         mut actual: crate::Data,
         mut expected: crate::Data,
     ) -> (crate::Data, crate::Data) {
+        expected = expected.with_custom_formats(&self.formats);
         if expected.inner.filters.is_newlines_set() {
             expected = FilterNewlines.filter(expected);
         }
+        if expected.inner.filters.is_collapse_spaces_set() {
+            expected = FilterCollapseSpaces.filter(expected);
+        }
+        if expected.inner.filters.is_trim_trailing_whitespace_set() {
+            expected = FilterTrimTrailingWhitespace.filter(expected);
+        }
+        if expected.inner.filters.is_remove_blank_lines_set() {
+            expected = FilterRemoveBlankLines.filter(expected);
+        }
 
         // On `expected` being an error, make a best guess
         actual = actual.coerce_to(expected.against_format());
         actual = actual.coerce_to(expected.intended_format());
 
+        if self.strip_ansi || expected.inner.filters.is_strip_ansi_set() {
+            actual = FilterStripAnsi.filter(actual);
+        }
         if self.normalize_paths && expected.inner.filters.is_paths_set() {
             actual = FilterPaths.filter(actual);
         }
         if expected.inner.filters.is_newlines_set() {
             actual = FilterNewlines.filter(actual);
         }
+        if expected.inner.filters.is_collapse_spaces_set() {
+            actual = FilterCollapseSpaces.filter(actual);
+        }
+        if expected.inner.filters.is_trim_trailing_whitespace_set() {
+            actual = FilterTrimTrailingWhitespace.filter(actual);
+        }
+        if expected.inner.filters.is_remove_blank_lines_set() {
+            actual = FilterRemoveBlankLines.filter(actual);
+        }
 
         let mut normalize = NormalizeToExpected::new();
         if expected.inner.filters.is_redaction_set() {
@@ -141,19 +229,34 @@ impl Assert {
         if expected.inner.filters.is_unordered_set() {
             normalize = normalize.unordered();
         }
+        if let Some(tolerance) = self.float_tolerance {
+            normalize = normalize.float_tolerance(tolerance);
+        }
         actual = normalize.normalize(actual, &expected);
 
         (actual, expected)
     }
 
+    /// Act on the outcome of [`Assert::try_verify`]
     fn do_action(
         &self,
-        actual_name: Option<&dyn std::fmt::Display>,
+        result: Result<()>,
         actual: crate::Data,
         expected: crate::Data,
     ) -> Result<()> {
-        let result = self.try_verify(actual_name, &actual, &expected);
+        // Overwriting makes the snapshot match, so a patch would not apply
+        #[cfg(feature = "diff")]
+        self.write_patch(
+            result.is_ok() || self.action == Action::Overwrite,
+            &actual,
+            &expected,
+        );
         let Err(err) = result else {
+            if self.is_review() {
+                if let Some(source) = expected.source() {
+                    crate::data::pending::clear(source)?;
+                }
+            }
             return Ok(());
         };
         match self.action {
@@ -169,12 +272,29 @@ impl Assert {
                 );
                 Ok(())
             }
+            Action::Verify if self.review => {
+                if let Some(source) = expected.source() {
+                    match crate::data::pending::write(&actual, source) {
+                        Ok(pending) => {
+                            let message = self.palette.hint(format!(
+                                "Pending update written to {}",
+                                crate::dir::display_relpath(&pending)
+                            ));
+                            Err(Error::new(format_args!("{err}{message}")))
+                        }
+                        Err(message) => {
+                            Err(Error::new(format_args!("{err}Update failed: {message}")))
+                        }
+                    }
+                } else {
+                    Err(Error::new(format_args!("{err}")))
+                }
+            }
             Action::Verify => {
                 let message = if expected.source().is_none() {
                     crate::report::Styled::new(String::new(), Default::default())
                 } else if let Some(action_var) = self.action_var.as_deref() {
-                    self.palette
-                        .hint(format!("Update with {action_var}=overwrite"))
+                    self.palette.hint(self.update_hint(action_var))
                 } else {
                     crate::report::Styled::new(String::new(), Default::default())
                 };
@@ -197,6 +317,49 @@ impl Assert {
         }
     }
 
+    /// Whether mismatches are recorded as pending updates, see [`Assert::review`]
+    fn is_review(&self) -> bool {
+        self.review && self.action == Action::Verify
+    }
+
+    fn action_name(&self) -> &'static str {
+        if self.is_review() {
+            REVIEW_ENV_VALUE
+        } else {
+            self.action.as_str()
+        }
+    }
+
+    #[cfg(feature = "diff")]
+    fn write_patch(&self, passed: bool, actual: &crate::Data, expected: &crate::Data) {
+        let Some(patch_dir) = self.patch_dir.as_ref() else {
+            return;
+        };
+        let Some(target) = expected.source().and_then(|source| source.as_path()) else {
+            return;
+        };
+        let result = if passed {
+            patch_dir.clear(target)
+        } else {
+            patch_dir.write(target, actual).map(|_| ())
+        };
+        if let Err(err) = result {
+            use std::io::Write;
+
+            let _ = writeln!(stderr(), "{}: {}", self.palette.warn("Patch failed"), err);
+        }
+    }
+
+    fn update_hint(&self, action_var: &str) -> String {
+        if self.overwrite_refused {
+            format!(
+                "Refusing {action_var}=overwrite in CI, set {ALLOW_CI_OVERWRITE_ENV}=1 to allow it"
+            )
+        } else {
+            format!("Update with {action_var}=overwrite")
+        }
+    }
+
     fn try_verify(
         &self,
         actual_name: Option<&dyn std::fmt::Display>,
@@ -221,6 +384,23 @@ impl Assert {
     }
 }
 
+/// Name a comparison for a [`Report`][crate::report::Report]
+///
+/// A test can make several comparisons, so this is qualified by what is being compared.
+fn report_name(actual_name: Option<&dyn std::fmt::Display>, expected: &crate::Data) -> String {
+    let mut name = std::thread::current()
+        .name()
+        .unwrap_or("<unnamed>")
+        .to_owned();
+    if let Some(actual_name) = actual_name {
+        name.push_str(&format!(" {actual_name}"));
+    }
+    if let Some(source) = expected.source() {
+        name.push_str(&format!(" ({source})"));
+    }
+    name
+}
+
 /// # Directory Assertions
 #[cfg(feature = "dir")]
 impl Assert {
@@ -320,6 +500,21 @@ impl Assert {
                         let _ = diff.write(&mut buffer, self.palette);
                         match self.action {
                             Action::Skip => unreachable!("Bailed out earlier"),
+                            Action::Verify if self.review => {
+                                ok = false;
+                                if let Err(err) = diff.write_pending() {
+                                    let path = diff
+                                        .expected_path()
+                                        .expect("always present when review can fail");
+                                    let _ = writeln!(
+                                        &mut buffer,
+                                        "{} to review {}: {}",
+                                        self.palette.error("Failed"),
+                                        path.display(),
+                                        err
+                                    );
+                                }
+                            }
                             Action::Ignore | Action::Verify => {
                                 ok = false;
                             }
@@ -351,7 +546,9 @@ impl Assert {
                         let _ =
                             write!(stderr(), "{}", self.palette.warn("Ignoring above failures"));
                     }
-                    Action::Verify => unreachable!("Something had to fail to get here"),
+                    Action::Verify => {
+                        unreachable!("Something had to fail to get here")
+                    }
                     Action::Overwrite => {
                         let _ = write!(
                             stderr(),
@@ -364,21 +561,30 @@ impl Assert {
                 match self.action {
                     Action::Skip => unreachable!("Bailed out earlier"),
                     Action::Ignore => unreachable!("Shouldn't be able to fail"),
+                    Action::Verify if self.review => {
+                        use std::fmt::Write;
+                        writeln!(
+                            &mut buffer,
+                            "{}",
+                            self.palette
+                                .hint("Pending updates written for above failures")
+                        )
+                        .unwrap();
+                    }
                     Action::Verify => {
                         use std::fmt::Write;
                         if let Some(action_var) = self.action_var.as_deref() {
                             writeln!(
                                 &mut buffer,
                                 "{}",
-                                self.palette
-                                    .hint(format_args!("Update with {action_var}=overwrite"))
+                                self.palette.hint(self.update_hint(action_var))
                             )
                             .unwrap();
                         }
                     }
                     Action::Overwrite => {}
                 }
-                panic!("{}", buffer);
+                Error::new(buffer).panic();
             }
         }
     }
@@ -386,17 +592,68 @@ impl Assert {
 
 /// # Customize Behavior
 impl Assert {
-    /// Override the color palette
+    /// Override the color palette, including its [`DiffLayout`][crate::report::DiffLayout] and
+    /// [`DiffOptions`][crate::report::DiffOptions]
     pub fn palette(mut self, palette: crate::report::Palette) -> Self {
         self.palette = palette;
         self
     }
 
+    /// Override how differences are shown on failure
+    ///
+    /// The default is read from [`DEFAULT_DIFF_ENV`][crate::report::DEFAULT_DIFF_ENV], if set,
+    /// like `SNAPSHOTS_DIFF=side-by-side`.  An invalid value is ignored with a warning.
+    pub fn diff_layout(mut self, layout: crate::report::DiffLayout) -> Self {
+        self.palette = self.palette.diff_layout(layout);
+        self
+    }
+
+    /// Override how much context is shown around differences and how long to spend finding them
+    ///
+    /// The default is read from
+    /// [`DEFAULT_DIFF_OPTIONS_ENV`][crate::report::DEFAULT_DIFF_OPTIONS_ENV], if set, like
+    /// `SNAPSHOTS_DIFF_OPTIONS=elide=never` to show the full diff.  An invalid value is ignored with a warning.
+    pub fn diff_options(mut self, options: crate::report::DiffOptions) -> Self {
+        self.palette = self.palette.diff_options(options);
+        self
+    }
+
+    /// Record each comparison in a machine-readable [`Report`][crate::report::Report]
+    ///
+    /// The default is to report to the path in
+    /// [`DEFAULT_REPORT_ENV`][crate::report::DEFAULT_REPORT_ENV], if set.
+    pub fn report(mut self, report: crate::report::Report) -> Self {
+        self.report = Some(report);
+        self
+    }
+
+    /// Write a [`PatchDir`][crate::report::PatchDir] `.patch` file for each snapshot file that
+    /// does not match
+    ///
+    /// The default is to write to the directory in
+    /// [`DEFAULT_PATCH_DIR_ENV`][crate::report::DEFAULT_PATCH_DIR_ENV], if set.
+    #[cfg(feature = "diff")]
+    pub fn patch_dir(mut self, patch_dir: crate::report::PatchDir) -> Self {
+        self.patch_dir = Some(patch_dir);
+        self
+    }
+
     /// Read the failure action from an environment variable
+    ///
+    /// Overwriting is refused in CI, see [`is_overwrite_allowed`].  A value of `review` verifies
+    /// with [`Assert::review`].
     pub fn action_env(mut self, var_name: &str) -> Self {
+        let value = std::env::var_os(var_name);
+        let requested = value.as_deref().and_then(Action::with_env_value);
         let action = Action::with_env_var(var_name);
-        self.action = action.unwrap_or(self.action);
+        self.review = value.as_deref() == Some(REVIEW_ENV_VALUE.as_ref());
+        self.action = if self.review {
+            Action::Verify
+        } else {
+            action.unwrap_or(self.action)
+        };
         self.action_var = Some(var_name.to_owned());
+        self.overwrite_refused = requested == Some(Action::Overwrite) && action != requested;
         self
     }
 
@@ -404,6 +661,17 @@ impl Assert {
     pub fn action(mut self, action: Action) -> Self {
         self.action = action;
         self.action_var = None;
+        self.overwrite_refused = false;
+        self
+    }
+
+    /// Specify whether a mismatch with [`Action::Verify`] records `actual` as a
+    /// [pending update][crate::data::PendingSnapshot] to accept or reject later
+    ///
+    /// The default is to only fail.  This is enabled by `SNAPSHOTS=review` with
+    /// [`Assert::action_env`].
+    pub fn review(mut self, yes: bool) -> Self {
+        self.review = yes;
         self
     }
 
@@ -419,6 +687,54 @@ impl Assert {
         self.redact_with(substitutions)
     }
 
+    /// Infer a [`SnapshotFormat`][crate::data::SnapshotFormat] from `expected` snapshot file
+    /// extensions
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use snapbox::Assert;
+    /// # use snapbox::file;
+    /// # use snapbox::data::CustomFormat;
+    /// # struct Words;
+    /// # impl snapbox::data::SnapshotFormat for Words {
+    /// #     type Value = String;
+    /// #     fn name(&self) -> &'static str { "words" }
+    /// #     fn ext(&self) -> &'static str { "words" }
+    /// #     fn parse(&self, raw: &str) -> snapbox::assert::Result<String> { Ok(raw.to_owned()) }
+    /// #     fn render(&self, value: &String) -> String { value.clone() }
+    /// # }
+    /// static WORDS: Words = Words;
+    ///
+    /// let actual = "hello world";
+    /// Assert::new()
+    ///   .register_format(CustomFormat::new(&WORDS))
+    ///   .eq(actual, file!["output.words"]);
+    /// ```
+    pub fn register_format(mut self, format: crate::data::CustomFormat) -> Self {
+        self.formats.push(format);
+        self
+    }
+
+    /// Treat numbers as equal when they are within `tolerance` of each other
+    ///
+    /// This applies to JSON numbers and to `[~1.5]` / `"{~1.5}"` placeholders that do not
+    /// specify their own tolerance.
+    ///
+    /// By default, JSON numbers must be equal and placeholders allow for rounding differences.
+    pub fn float_tolerance(mut self, tolerance: f64) -> Self {
+        self.float_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Specify whether ANSI escape codes, like colors, should be removed from `actual`
+    ///
+    /// The default is to keep them.  See also [`IntoData::strip_ansi`].
+    pub fn strip_ansi(mut self, yes: bool) -> Self {
+        self.strip_ansi = yes;
+        self
+    }
+
     /// Specify whether text should have path separators normalized
     ///
     /// The default is normalized
@@ -443,10 +759,55 @@ impl Default for Assert {
         Self {
             action: Default::default(),
             action_var: Default::default(),
+            overwrite_refused: false,
+            review: false,
             normalize_paths: true,
             substitutions: Default::default(),
-            palette: crate::report::Palette::color(),
+            formats: Default::default(),
+            float_tolerance: Default::default(),
+            strip_ansi: false,
+            report: crate::report::Report::with_env_var(crate::report::DEFAULT_REPORT_ENV),
+            #[cfg(feature = "diff")]
+            patch_dir: crate::report::PatchDir::with_env_var(crate::report::DEFAULT_PATCH_DIR_ENV),
+            palette: crate::report::Palette::color()
+                .diff_layout(env_or_default(
+                    crate::report::DEFAULT_DIFF_ENV,
+                    |value| crate::report::DiffLayout::with_env_value(value),
+                    &INVALID_DIFF_ENV,
+                ))
+                .diff_options(env_or_default(
+                    crate::report::DEFAULT_DIFF_OPTIONS_ENV,
+                    |value| crate::report::DiffOptions::with_env_value(value),
+                    &INVALID_DIFF_OPTIONS_ENV,
+                )),
         }
         .redact_with(crate::Redactions::with_exe())
     }
 }
+
+static INVALID_DIFF_ENV: std::sync::Once = std::sync::Once::new();
+static INVALID_DIFF_OPTIONS_ENV: std::sync::Once = std::sync::Once::new();
+
+/// Parse `var`, if set, warning once per process when it is invalid
+fn env_or_default<T: Default>(
+    var: &str,
+    parse: impl FnOnce(&std::ffi::OsStr) -> Option<T>,
+    warned: &std::sync::Once,
+) -> T {
+    let Some(value) = std::env::var_os(var) else {
+        return T::default();
+    };
+    parse(&value).unwrap_or_else(|| {
+        warned.call_once(|| {
+            use std::io::Write;
+
+            let _ = writeln!(
+                stderr(),
+                "{}: {var}={}",
+                crate::report::Palette::color().warn("Ignoring invalid"),
+                value.to_string_lossy()
+            );
+        });
+        T::default()
+    })
+}
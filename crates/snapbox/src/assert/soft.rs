@@ -0,0 +1,79 @@
+//! Collect assertion failures, see [`Assert::soft`][crate::Assert::soft]
+
+use std::cell::RefCell;
+use std::panic::Location;
+
+use super::Error;
+
+thread_local! {
+    static FAILURES: RefCell<Option<Vec<Failure>>> = const { RefCell::new(None) };
+}
+
+struct Failure {
+    error: Error,
+    location: &'static Location<'static>,
+}
+
+/// Run `f`, collecting assertion failures on this thread until the end
+pub(crate) fn run<R>(f: impl FnOnce() -> R) -> R {
+    let is_nested = FAILURES.with_borrow(|failures| failures.is_some());
+    if is_nested {
+        // Let the outermost scope report failures
+        return f();
+    }
+
+    let guard = Guard;
+    FAILURES.set(Some(Vec::new()));
+    let result = f();
+    let failures = guard.finish();
+    if !failures.is_empty() {
+        let total = failures.len();
+        let mut message = format!("{total} snapshot assertion(s) failed\n");
+        for (i, failure) in failures.into_iter().enumerate() {
+            message.push_str(&format!(
+                "\n---- [{}/{total}] {}\n{}",
+                i + 1,
+                failure.location,
+                failure.error
+            ));
+        }
+        panic!("{message}");
+    }
+    result
+}
+
+/// Hold onto `error` if within [`run`], otherwise return it
+pub(crate) fn collect(error: Error, location: &'static Location<'static>) -> Option<Error> {
+    FAILURES.with_borrow_mut(|failures| {
+        if let Some(failures) = failures {
+            failures.push(Failure { error, location });
+            None
+        } else {
+            Some(error)
+        }
+    })
+}
+
+/// Ends the collection, even on panic
+struct Guard;
+
+impl Guard {
+    fn finish(self) -> Vec<Failure> {
+        FAILURES.take().unwrap_or_default()
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let failures = FAILURES.take().unwrap_or_default();
+        if !failures.is_empty() {
+            use std::io::Write as _;
+
+            // Only reachable on panic, so report what would otherwise be lost
+            let mut stderr = std::io::stderr();
+            for failure in failures {
+                let _ = writeln!(stderr, "---- {}\n{}", failure.location, failure.error);
+            }
+        }
+    }
+}
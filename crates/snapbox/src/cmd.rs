@@ -514,7 +514,7 @@ impl OutputAssert {
             writeln!(&mut buf, "{desc}").unwrap();
             self.write_stdout(&mut buf).unwrap();
             self.write_stderr(&mut buf).unwrap();
-            panic!("{}", buf);
+            crate::assert::Error::new(buf).panic();
         }
         self
     }
@@ -544,7 +544,7 @@ impl OutputAssert {
             writeln!(&mut buf, "{desc}").unwrap();
             self.write_stdout(&mut buf).unwrap();
             self.write_stderr(&mut buf).unwrap();
-            panic!("{}", buf);
+            crate::assert::Error::new(buf).panic();
         }
         self
     }
@@ -566,7 +566,7 @@ impl OutputAssert {
             writeln!(&mut buf, "{desc}").unwrap();
             self.write_stdout(&mut buf).unwrap();
             self.write_stderr(&mut buf).unwrap();
-            panic!("{}", buf);
+            crate::assert::Error::new(buf).panic();
         }
         self
     }
@@ -598,7 +598,7 @@ impl OutputAssert {
             writeln!(&mut buf, "{desc}").unwrap();
             self.write_stdout(&mut buf).unwrap();
             self.write_stderr(&mut buf).unwrap();
-            panic!("{}", buf);
+            crate::assert::Error::new(buf).panic();
         }
         self
     }
@@ -0,0 +1,264 @@
+use std::any::Any;
+use std::any::TypeId;
+use std::sync::Arc;
+
+/// User-defined structure for [`Data`][crate::Data]
+///
+/// Implement this to snapshot formats that `snapbox` does not support natively.  See
+/// [`DataFormat::Custom`][crate::data::DataFormat::Custom] for selecting the format for `expected`
+/// data and [`Assert::register_format`][crate::Assert::register_format] for inferring it from
+/// snapshot file extensions.
+///
+/// [Filters][crate::filter] are not applied to custom values, see
+/// [`SnapshotFormat::normalize_to_expected`] for customizing how `actual` is compared.
+///
+/// # Examples
+///
+/// ```rust
+/// use snapbox::prelude::*;
+/// use snapbox::data::CustomFormat;
+/// use snapbox::data::DataFormat;
+/// use snapbox::data::SnapshotFormat;
+///
+/// struct Words;
+///
+/// impl SnapshotFormat for Words {
+///     type Value = Vec<String>;
+///
+///     fn name(&self) -> &'static str {
+///         "words"
+///     }
+///
+///     fn ext(&self) -> &'static str {
+///         "words"
+///     }
+///
+///     fn parse(&self, raw: &str) -> snapbox::assert::Result<Self::Value> {
+///         Ok(raw.split_whitespace().map(|w| w.to_owned()).collect())
+///     }
+///
+///     fn render(&self, value: &Self::Value) -> String {
+///         value.iter().map(|w| format!("{w}\n")).collect()
+///     }
+/// }
+///
+/// static WORDS: Words = Words;
+///
+/// let expected = "hello\nworld".is(DataFormat::Custom(CustomFormat::new(&WORDS)));
+/// snapbox::assert_data_eq!("hello    world", expected);
+/// ```
+pub trait SnapshotFormat: Send + Sync + 'static {
+    type Value: Clone + PartialEq + Send + Sync + 'static;
+
+    /// Name for the format, used in reports
+    fn name(&self) -> &'static str;
+
+    /// Assumed file extension for the format
+    fn ext(&self) -> &'static str;
+
+    /// Convert snapshot text into a value
+    fn parse(&self, raw: &str) -> crate::assert::Result<Self::Value>;
+
+    /// Convert a value into snapshot text
+    fn render(&self, value: &Self::Value) -> String;
+
+    /// Adjust `actual` based on `expected`, see [`NormalizeToExpected`][crate::filter::NormalizeToExpected]
+    ///
+    /// `redactions` is applicable when the `expected` data uses the default
+    /// [`filters`][crate::filter].
+    fn normalize_to_expected(
+        &self,
+        actual: Self::Value,
+        expected: &Self::Value,
+        redactions: &crate::Redactions,
+    ) -> Self::Value {
+        let _ = (expected, redactions);
+        actual
+    }
+}
+
+/// Handle to a [`SnapshotFormat`] for use in [`DataFormat::Custom`][crate::data::DataFormat::Custom]
+///
+/// Handles are equal when they refer to the same `&'static` format, so formats of the same type
+/// but with different settings stay distinct.
+#[derive(Copy, Clone)]
+pub struct CustomFormat {
+    inner: &'static dyn ErasedFormat,
+}
+
+impl CustomFormat {
+    pub fn new<F: SnapshotFormat>(format: &'static F) -> Self {
+        Self { inner: format }
+    }
+
+    /// See [`SnapshotFormat::name`]
+    pub fn name(self) -> &'static str {
+        self.inner.name()
+    }
+
+    /// See [`SnapshotFormat::ext`]
+    pub fn ext(self) -> &'static str {
+        self.inner.ext()
+    }
+
+    /// Find the format whose [`SnapshotFormat::ext`] matches `path`
+    pub(crate) fn infer(formats: &[Self], path: &std::path::Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        formats.iter().copied().find(|format| {
+            file_name
+                .strip_suffix(format.ext())
+                .and_then(|stem| stem.strip_suffix('.'))
+                .is_some_and(|stem| !stem.is_empty())
+        })
+    }
+
+    pub(crate) fn parse(self, raw: &str) -> crate::assert::Result<CustomValue> {
+        let value = self.inner.parse(raw)?;
+        Ok(CustomValue {
+            format: self,
+            value,
+        })
+    }
+}
+
+impl std::fmt::Debug for CustomFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomFormat").field(&self.name()).finish()
+    }
+}
+
+/// Formats are identified by their type, as that determines how values are compared, and by
+/// their instance, as that may be configured differently
+impl PartialEq for CustomFormat {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.format_id() == other.inner.format_id()
+            && std::ptr::addr_eq(self.inner, other.inner)
+    }
+}
+
+impl Eq for CustomFormat {}
+
+impl std::hash::Hash for CustomFormat {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.format_id().hash(state);
+        std::ptr::from_ref(self.inner).cast::<()>().hash(state);
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct CustomValue {
+    format: CustomFormat,
+    value: AnyValue,
+}
+
+impl CustomValue {
+    pub(crate) fn format(&self) -> CustomFormat {
+        self.format
+    }
+
+    pub(crate) fn render(&self) -> String {
+        self.format.inner.render(&self.value)
+    }
+
+    pub(crate) fn normalize_to_expected(
+        self,
+        expected: &CustomValue,
+        redactions: &crate::Redactions,
+    ) -> Self {
+        if self.format != expected.format {
+            return self;
+        }
+        let value =
+            self.format
+                .inner
+                .normalize_to_expected(&self.value, &expected.value, redactions);
+        Self {
+            format: self.format,
+            value,
+        }
+    }
+}
+
+impl std::fmt::Debug for CustomValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomValue")
+            .field("format", &self.format)
+            .field("value", &self.render())
+            .finish()
+    }
+}
+
+impl PartialEq for CustomValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.format == other.format && self.format.inner.eq(&self.value, &other.value)
+    }
+}
+
+type AnyValue = Arc<dyn Any + Send + Sync>;
+
+/// Object-safe version of [`SnapshotFormat`]
+trait ErasedFormat: Send + Sync {
+    fn format_id(&self) -> TypeId;
+
+    fn name(&self) -> &'static str;
+
+    fn ext(&self) -> &'static str;
+
+    fn parse(&self, raw: &str) -> crate::assert::Result<AnyValue>;
+
+    fn render(&self, value: &AnyValue) -> String;
+
+    fn eq(&self, left: &AnyValue, right: &AnyValue) -> bool;
+
+    fn normalize_to_expected(
+        &self,
+        actual: &AnyValue,
+        expected: &AnyValue,
+        redactions: &crate::Redactions,
+    ) -> AnyValue;
+}
+
+impl<F: SnapshotFormat> ErasedFormat for F {
+    fn format_id(&self) -> TypeId {
+        TypeId::of::<F>()
+    }
+
+    fn name(&self) -> &'static str {
+        SnapshotFormat::name(self)
+    }
+
+    fn ext(&self) -> &'static str {
+        SnapshotFormat::ext(self)
+    }
+
+    fn parse(&self, raw: &str) -> crate::assert::Result<AnyValue> {
+        let value = SnapshotFormat::parse(self, raw)?;
+        Ok(Arc::new(value))
+    }
+
+    fn render(&self, value: &AnyValue) -> String {
+        SnapshotFormat::render(self, downcast::<F>(value))
+    }
+
+    fn eq(&self, left: &AnyValue, right: &AnyValue) -> bool {
+        downcast::<F>(left) == downcast::<F>(right)
+    }
+
+    fn normalize_to_expected(
+        &self,
+        actual: &AnyValue,
+        expected: &AnyValue,
+        redactions: &crate::Redactions,
+    ) -> AnyValue {
+        let actual = downcast::<F>(actual).clone();
+        let expected = downcast::<F>(expected);
+        let normalized = SnapshotFormat::normalize_to_expected(self, actual, expected, redactions);
+        Arc::new(normalized)
+    }
+}
+
+fn downcast<F: SnapshotFormat>(value: &AnyValue) -> &F::Value {
+    value
+        .downcast_ref::<F::Value>()
+        .expect("values are only compared within the `SnapshotFormat` that created them")
+}
@@ -38,6 +38,26 @@ impl FilterSet {
         self
     }
 
+    pub(crate) fn strip_ansi(mut self) -> Self {
+        self.set(Self::STRIP_ANSI);
+        self
+    }
+
+    pub(crate) fn trim_trailing_whitespace(mut self) -> Self {
+        self.set(Self::TRIM_TRAILING_WHITESPACE);
+        self
+    }
+
+    pub(crate) fn collapse_spaces(mut self) -> Self {
+        self.set(Self::COLLAPSE_SPACES);
+        self
+    }
+
+    pub(crate) fn remove_blank_lines(mut self) -> Self {
+        self.set(Self::REMOVE_BLANK_LINES);
+        self
+    }
+
     pub(crate) fn against(mut self, format: DataFormat) -> Self {
         self.against = Some(format);
         self
@@ -59,6 +79,22 @@ impl FilterSet {
         self.is_set(Self::UNORDERED)
     }
 
+    pub(crate) const fn is_strip_ansi_set(&self) -> bool {
+        self.is_set(Self::STRIP_ANSI)
+    }
+
+    pub(crate) const fn is_trim_trailing_whitespace_set(&self) -> bool {
+        self.is_set(Self::TRIM_TRAILING_WHITESPACE)
+    }
+
+    pub(crate) const fn is_collapse_spaces_set(&self) -> bool {
+        self.is_set(Self::COLLAPSE_SPACES)
+    }
+
+    pub(crate) const fn is_remove_blank_lines_set(&self) -> bool {
+        self.is_set(Self::REMOVE_BLANK_LINES)
+    }
+
     pub(crate) const fn get_against(&self) -> Option<DataFormat> {
         self.against
     }
@@ -69,6 +105,10 @@ impl FilterSet {
     const NEWLINES: usize = 1 << 1;
     const PATHS: usize = 1 << 2;
     const UNORDERED: usize = 1 << 3;
+    const STRIP_ANSI: usize = 1 << 4;
+    const TRIM_TRAILING_WHITESPACE: usize = 1 << 5;
+    const COLLAPSE_SPACES: usize = 1 << 6;
+    const REMOVE_BLANK_LINES: usize = 1 << 7;
 
     fn set(&mut self, flag: usize) -> &mut Self {
         self.flags |= flag;
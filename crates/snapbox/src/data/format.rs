@@ -5,6 +5,8 @@ pub enum DataFormat {
     /// Processing of the [`Data`][crate::Data] failed
     Error,
     /// Non-textual, opaque data
+    ///
+    /// Snapshot files ending in `.hex` store this as a reviewable hexdump
     Binary,
     #[default]
     Text,
@@ -13,10 +15,24 @@ pub enum DataFormat {
     /// Streamed JSON output according to <https://jsonlines.org/>
     #[cfg(feature = "json")]
     JsonLines,
+    /// [YAML](https://yaml.org/), compared as structured data like JSON
+    #[cfg(feature = "yaml")]
+    Yaml,
+    /// [TOML](https://toml.io/), compared as structured data like JSON
+    #[cfg(feature = "toml")]
+    Toml,
+    /// Comma-separated values, compared record by record
+    #[cfg(feature = "csv")]
+    Csv,
+    /// Tab-separated values, compared record by record
+    #[cfg(feature = "csv")]
+    Tsv,
     /// [ANSI escape codes](https://en.wikipedia.org/wiki/ANSI_escape_code#DOS_and_Windows)
     /// rendered as [svg](https://docs.rs/anstyle-svg)
     #[cfg(feature = "term-svg")]
     TermSvg,
+    /// User-defined structure, see [`SnapshotFormat`][crate::data::SnapshotFormat]
+    Custom(crate::data::CustomFormat),
 }
 
 impl DataFormat {
@@ -30,8 +46,17 @@ impl DataFormat {
             Self::Json => "json",
             #[cfg(feature = "json")]
             Self::JsonLines => "jsonl",
+            #[cfg(feature = "yaml")]
+            Self::Yaml => "yaml",
+            #[cfg(feature = "toml")]
+            Self::Toml => "toml",
+            #[cfg(feature = "csv")]
+            Self::Csv => "csv",
+            #[cfg(feature = "csv")]
+            Self::Tsv => "tsv",
             #[cfg(feature = "term-svg")]
             Self::TermSvg => "term.svg",
+            Self::Custom(format) => format.ext(),
         }
     }
 }
@@ -46,6 +71,9 @@ impl From<&std::path::Path> for DataFormat {
         while let Some((_, new_ext)) = ext.split_once('.') {
             ext = new_ext;
             match ext {
+                "hex" => {
+                    return DataFormat::Binary;
+                }
                 #[cfg(feature = "json")]
                 "json" => {
                     return DataFormat::Json;
@@ -54,6 +82,22 @@ impl From<&std::path::Path> for DataFormat {
                 "jsonl" => {
                     return DataFormat::JsonLines;
                 }
+                #[cfg(feature = "yaml")]
+                "yaml" | "yml" => {
+                    return DataFormat::Yaml;
+                }
+                #[cfg(feature = "toml")]
+                "toml" => {
+                    return DataFormat::Toml;
+                }
+                #[cfg(feature = "csv")]
+                "csv" => {
+                    return DataFormat::Csv;
+                }
+                #[cfg(feature = "csv")]
+                "tsv" => {
+                    return DataFormat::Tsv;
+                }
                 #[cfg(feature = "term-svg")]
                 "term.svg" => {
                     return Self::TermSvg;
@@ -79,6 +123,22 @@ mod test {
         let jsonl = DataFormat::JsonLines;
         #[cfg(not(feature = "json"))]
         let jsonl = DataFormat::Text;
+        #[cfg(feature = "yaml")]
+        let yaml = DataFormat::Yaml;
+        #[cfg(not(feature = "yaml"))]
+        let yaml = DataFormat::Text;
+        #[cfg(feature = "toml")]
+        let toml = DataFormat::Toml;
+        #[cfg(not(feature = "toml"))]
+        let toml = DataFormat::Text;
+        #[cfg(feature = "csv")]
+        let csv = DataFormat::Csv;
+        #[cfg(not(feature = "csv"))]
+        let csv = DataFormat::Text;
+        #[cfg(feature = "csv")]
+        let tsv = DataFormat::Tsv;
+        #[cfg(not(feature = "csv"))]
+        let tsv = DataFormat::Text;
         #[cfg(feature = "term-svg")]
         let term_svg = DataFormat::TermSvg;
         #[cfg(not(feature = "term-svg"))]
@@ -89,12 +149,25 @@ mod test {
             ("foo.txt", DataFormat::Text),
             (".foo.txt", DataFormat::Text),
             ("foo.stdout.txt", DataFormat::Text),
+            ("foo.hex", DataFormat::Binary),
+            ("foo.stdout.hex", DataFormat::Binary),
             ("foo.json", json),
             ("foo.stdout.json", json),
             (".foo.json", json),
             ("foo.jsonl", jsonl),
             ("foo.stdout.jsonl", jsonl),
             (".foo.jsonl", jsonl),
+            ("foo.yaml", yaml),
+            ("foo.stdout.yaml", yaml),
+            (".foo.yaml", yaml),
+            ("foo.yml", yaml),
+            ("foo.toml", toml),
+            ("foo.stdout.toml", toml),
+            (".foo.toml", toml),
+            ("foo.csv", csv),
+            ("foo.stdout.csv", csv),
+            ("foo.tsv", tsv),
+            ("foo.stdout.tsv", tsv),
             ("foo.term.svg", term_svg),
             ("foo.stdout.term.svg", term_svg),
             (".foo.term.svg", term_svg),
@@ -0,0 +1,120 @@
+//! Reviewable, textual representation of binary data
+//!
+//! The layout follows `hexdump -C`:
+//! ```text
+//! 00000000  48 65 6c 6c 6f 2c 20 57  6f 72 6c 64 21 0a        |Hello, World!.|
+//! ```
+
+const BYTES_PER_LINE: usize = 16;
+
+pub(crate) fn is_hexdump_path(path: &std::path::Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("hex")
+}
+
+pub(crate) fn encode(data: &[u8]) -> String {
+    use std::fmt::Write as _;
+
+    let mut dump = String::new();
+    for (index, chunk) in data.chunks(BYTES_PER_LINE).enumerate() {
+        write!(&mut dump, "{:08x}  ", index * BYTES_PER_LINE).unwrap();
+        for i in 0..BYTES_PER_LINE {
+            if let Some(byte) = chunk.get(i) {
+                write!(&mut dump, "{byte:02x} ").unwrap();
+            } else {
+                dump.push_str("   ");
+            }
+            if i == BYTES_PER_LINE / 2 - 1 {
+                dump.push(' ');
+            }
+        }
+        dump.push_str(" |");
+        for byte in chunk {
+            let c = if byte.is_ascii_graphic() || *byte == b' ' {
+                *byte as char
+            } else {
+                '.'
+            };
+            dump.push(c);
+        }
+        dump.push_str("|\n");
+    }
+    dump
+}
+
+pub(crate) fn decode(dump: &str) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    for (line_num, line) in dump.lines().enumerate() {
+        let line_num = line_num + 1;
+        let mut tokens = line.split_whitespace();
+        let Some(offset) = tokens.next() else {
+            continue;
+        };
+        let offset = usize::from_str_radix(offset, 16)
+            .map_err(|_err| format!("line {line_num}: invalid offset `{offset}`"))?;
+        if offset != data.len() {
+            return Err(format!(
+                "line {line_num}: expected offset {:08x}, found {offset:08x}",
+                data.len()
+            ));
+        }
+        for token in tokens {
+            if token.starts_with('|') {
+                break;
+            }
+            let byte = (token.len() == 2)
+                .then(|| u8::from_str_radix(token, 16).ok())
+                .flatten()
+                .ok_or_else(|| format!("line {line_num}: invalid byte `{token}`"))?;
+            data.push(byte);
+        }
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_partial_line() {
+        let actual = encode(b"Hello, World!\n");
+        let expected =
+            "00000000  48 65 6c 6c 6f 2c 20 57  6f 72 6c 64 21 0a        |Hello, World!.|\n";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn encode_multiple_lines() {
+        let data = (0..=20).collect::<Vec<u8>>();
+        let actual = encode(&data);
+        let expected = "\
+00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|
+00000010  10 11 12 13 14                                    |.....|
+";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn round_trip() {
+        let cases: &[&[u8]] = &[b"", b"|", b"| 00 |", &[0xff; 33], b"Hello, World!\n"];
+        for data in cases {
+            let dump = encode(data);
+            let actual = decode(&dump).unwrap();
+            assert_eq!(&actual, data, "{dump}");
+        }
+    }
+
+    #[test]
+    fn decode_errors() {
+        let cases = [
+            "zzzzzzzz  00",
+            "00000010  00",
+            "00000000  0g",
+            "00000000  000",
+            "00000000  00\n00000000  00",
+        ];
+        for dump in cases {
+            assert!(decode(dump).is_err(), "{dump}");
+        }
+    }
+}
@@ -1,18 +1,29 @@
 //! `actual` and `expected` [`Data`] for testing code
 
+mod custom;
 mod filters;
 mod format;
+mod hexdump;
+pub(crate) mod pending;
+mod prune;
 mod runtime;
 mod source;
 #[cfg(test)]
 mod tests;
 
+pub use custom::CustomFormat;
+pub use custom::SnapshotFormat;
 pub use format::DataFormat;
+pub use pending::PENDING_SUFFIX;
+pub use pending::PendingSnapshot;
+pub use prune::clear_used_snapshots;
+pub use prune::unused_snapshots;
 pub use source::DataSource;
 pub use source::Inline;
 #[doc(hidden)]
 pub use source::Position;
 
+pub(crate) use custom::CustomValue;
 use filters::FilterSet;
 
 /// Capture the pretty debug representation of a value
@@ -104,6 +115,82 @@ pub trait IntoData: Sized {
         self.into_data().unordered()
     }
 
+    /// Remove ANSI escape codes, like colors, from `actual` before comparing
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use snapbox::prelude::*;
+    /// use snapbox::str;
+    /// use snapbox::assert_data_eq;
+    ///
+    /// let actual = "\x1b[1m\x1b[32mok\x1b[0m";
+    /// assert_data_eq!(actual, str!["ok"].strip_ansi());
+    /// ```
+    fn strip_ansi(self) -> Data {
+        self.into_data().strip_ansi()
+    }
+
+    /// Trim whitespace at the end of each line before comparing
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use snapbox::prelude::*;
+    /// use snapbox::str;
+    /// use snapbox::assert_data_eq;
+    ///
+    /// let actual = "name   \nvalue\t\n";
+    /// assert_data_eq!(actual, str![[r#"
+    /// name
+    /// value
+    ///
+    /// "#]].trim_trailing_whitespace());
+    /// ```
+    fn trim_trailing_whitespace(self) -> Data {
+        self.into_data().trim_trailing_whitespace()
+    }
+
+    /// Treat runs of spaces and tabs as a single space
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use snapbox::prelude::*;
+    /// use snapbox::str;
+    /// use snapbox::assert_data_eq;
+    ///
+    /// let actual = "name    value\ncrate   snapbox\n";
+    /// assert_data_eq!(actual, str![[r#"
+    /// name value
+    /// crate snapbox
+    ///
+    /// "#]].collapse_spaces());
+    /// ```
+    fn collapse_spaces(self) -> Data {
+        self.into_data().collapse_spaces()
+    }
+
+    /// Remove lines that are empty or only contain whitespace before comparing
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use snapbox::prelude::*;
+    /// use snapbox::str;
+    /// use snapbox::assert_data_eq;
+    ///
+    /// let actual = "header\n\n\nbody\n";
+    /// assert_data_eq!(actual, str![[r#"
+    /// header
+    /// body
+    ///
+    /// "#]].remove_blank_lines());
+    /// ```
+    fn remove_blank_lines(self) -> Data {
+        self.into_data().remove_blank_lines()
+    }
+
     /// Initialize as [`format`][DataFormat] or [`Error`][DataFormat::Error]
     ///
     /// This is generally used for `expected` data
@@ -178,6 +265,80 @@ pub trait IntoData: Sized {
         self.is_jsonlines()
     }
 
+    /// Initialize as yaml or [`Error`][DataFormat::Error]
+    ///
+    /// This is generally used for `expected` data
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "yaml")] {
+    /// use snapbox::prelude::*;
+    /// use snapbox::str;
+    ///
+    /// let expected = str![[r#"hello: world"#]]
+    ///     .is_yaml();
+    /// assert_eq!(expected.format(), snapbox::data::DataFormat::Yaml);
+    /// # }
+    /// ```
+    #[cfg(feature = "yaml")]
+    fn is_yaml(self) -> Data {
+        self.is(DataFormat::Yaml)
+    }
+
+    /// Initialize as toml or [`Error`][DataFormat::Error]
+    ///
+    /// This is generally used for `expected` data
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "toml")] {
+    /// use snapbox::prelude::*;
+    /// use snapbox::str;
+    ///
+    /// let expected = str![[r#"hello = "world""#]]
+    ///     .is_toml();
+    /// assert_eq!(expected.format(), snapbox::data::DataFormat::Toml);
+    /// # }
+    /// ```
+    #[cfg(feature = "toml")]
+    fn is_toml(self) -> Data {
+        self.is(DataFormat::Toml)
+    }
+
+    /// Initialize as csv
+    ///
+    /// This is generally used for `expected` data
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "csv")] {
+    /// use snapbox::prelude::*;
+    /// use snapbox::str;
+    ///
+    /// let expected = str![[r#"
+    /// name,count
+    /// hello,[..]
+    /// "#]]
+    ///     .is_csv();
+    /// assert_eq!(expected.format(), snapbox::data::DataFormat::Csv);
+    /// # }
+    /// ```
+    #[cfg(feature = "csv")]
+    fn is_csv(self) -> Data {
+        self.is(DataFormat::Csv)
+    }
+
+    /// Initialize as tsv
+    ///
+    /// This is generally used for `expected` data
+    #[cfg(feature = "csv")]
+    fn is_tsv(self) -> Data {
+        self.is(DataFormat::Tsv)
+    }
+
     /// Initialize as Term SVG
     ///
     /// This is generally used for `expected` data
@@ -399,8 +560,18 @@ pub(crate) enum DataValue {
     // Always a `Value::Array` but using `Value` for easier bookkeeping
     #[cfg(feature = "json")]
     JsonLines(serde_json::Value),
+    #[cfg(feature = "yaml")]
+    Yaml(serde_json::Value),
+    // Always a `Value::Object` but using `Value` for easier bookkeeping
+    #[cfg(feature = "toml")]
+    Toml(serde_json::Value, TomlDatetimes),
+    #[cfg(feature = "csv")]
+    Csv(Vec<Vec<String>>),
+    #[cfg(feature = "csv")]
+    Tsv(Vec<Vec<String>>),
     #[cfg(feature = "term-svg")]
     TermSvg(String),
+    Custom(CustomValue),
 }
 
 /// # Constructors
@@ -465,6 +636,97 @@ impl Data {
         self.inner.filters = self.inner.filters.unordered();
         self
     }
+
+    /// Remove ANSI escape codes, like colors, from `actual` before comparing
+    pub fn strip_ansi(mut self) -> Self {
+        self.inner.filters = self.inner.filters.strip_ansi();
+        self
+    }
+
+    /// Trim whitespace at the end of each line before comparing
+    pub fn trim_trailing_whitespace(mut self) -> Self {
+        self.inner.filters = self.inner.filters.trim_trailing_whitespace();
+        self
+    }
+
+    /// Treat runs of spaces and tabs as a single space
+    pub fn collapse_spaces(mut self) -> Self {
+        self.inner.filters = self.inner.filters.collapse_spaces();
+        self
+    }
+
+    /// Remove lines that are empty or only contain whitespace before comparing
+    pub fn remove_blank_lines(mut self) -> Self {
+        self.inner.filters = self.inner.filters.remove_blank_lines();
+        self
+    }
+
+    /// Focus on the sub-tree at a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901)
+    ///
+    /// If the pointer does not resolve, this becomes an [`Error`][DataFormat::Error].
+    ///
+    /// JSON Lines are treated as an array of lines, so `/0` selects the first line, like with
+    /// [`Redactions::insert_at`][crate::Redactions::insert_at].
+    ///
+    /// The sub-tree has no [`source`][Data::source], so selecting from a snapshot file is not
+    /// overwritten with just the sub-tree.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "json")] {
+    /// use snapbox::prelude::*;
+    /// use snapbox::str;
+    /// use snapbox::assert_data_eq;
+    ///
+    /// let actual = serde_json::json!({"items": [{"meta": {"id": 5}, "body": "..."}]});
+    /// let actual = actual.into_json().select("/items/0/meta");
+    /// assert_data_eq!(actual, str![[r#"{"id": 5}"#]].is_json());
+    /// # }
+    /// ```
+    #[cfg(feature = "structured-data")]
+    pub fn select(self, pointer: &str) -> Self {
+        let format = self.format();
+        let filters = self.inner.filters;
+        let value = match self.inner.value {
+            DataValue::Error(inner) => Ok(DataValue::Error(inner)),
+            #[cfg(feature = "json")]
+            DataValue::Json(inner) | DataValue::JsonLines(inner) => {
+                select_value(inner, pointer).map(DataValue::Json)
+            }
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(inner) => select_value(inner, pointer).map(DataValue::Yaml),
+            #[cfg(feature = "toml")]
+            DataValue::Toml(inner, datetimes) => select_value(inner, pointer).and_then(|value| {
+                if value.is_object() {
+                    Ok(DataValue::Toml(
+                        value,
+                        select_toml_datetimes(datetimes, pointer),
+                    ))
+                } else {
+                    Err(format!("`{pointer}` is not a table"))
+                }
+            }),
+            _ => Err(format!("cannot select `{pointer}` from {format:?}")),
+        };
+        let value = value.unwrap_or_else(|err| {
+            DataValue::Error(DataError {
+                error: err.into(),
+                intended: match format {
+                    #[cfg(feature = "json")]
+                    DataFormat::JsonLines => DataFormat::Json,
+                    format => format,
+                },
+            })
+        });
+        Self {
+            inner: Box::new(DataInner {
+                value,
+                source: None,
+                filters,
+            }),
+        }
+    }
 }
 
 /// # Assertion frameworks operations
@@ -495,16 +757,32 @@ impl Data {
         path: &std::path::Path,
         data_format: Option<DataFormat>,
     ) -> crate::assert::Result<Self> {
+        prune::record(path);
         let data =
             std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let data = if hexdump::is_hexdump_path(path) {
+            let dump = String::from_utf8(data)
+                .map_err(|_err| format!("Failed to read {}: invalid UTF-8", path.display()))?;
+            hexdump::decode(&dump)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        } else {
+            data
+        };
         let data = Self::binary(data);
         let data = match data_format {
             Some(df) => data.is(df),
             None => {
                 let inferred_format = DataFormat::from(path);
                 match inferred_format {
+                    DataFormat::Binary => data,
                     #[cfg(feature = "json")]
                     DataFormat::Json | DataFormat::JsonLines => data.coerce_to(inferred_format),
+                    #[cfg(feature = "yaml")]
+                    DataFormat::Yaml => data.coerce_to(inferred_format),
+                    #[cfg(feature = "toml")]
+                    DataFormat::Toml => data.coerce_to(inferred_format),
+                    #[cfg(feature = "csv")]
+                    DataFormat::Csv | DataFormat::Tsv => data.coerce_to(inferred_format),
                     #[cfg(feature = "term-svg")]
                     DataFormat::TermSvg => {
                         let data = data.coerce_to(DataFormat::Text);
@@ -534,11 +812,20 @@ impl Data {
                 format!("Failed to create parent dir for {}: {}", path.display(), e)
             })?;
         }
-        let bytes = self.to_bytes()?;
+        let bytes = self.to_file_bytes(path)?;
         std::fs::write(path, bytes)
             .map_err(|e| format!("Failed to write {}: {}", path.display(), e).into())
     }
 
+    /// Encode `self` as it would be stored at `path`
+    pub(crate) fn to_file_bytes(&self, path: &std::path::Path) -> crate::assert::Result<Vec<u8>> {
+        let mut bytes = self.to_bytes()?;
+        if hexdump::is_hexdump_path(path) {
+            bytes = hexdump::encode(&bytes).into_bytes();
+        }
+        Ok(bytes)
+    }
+
     /// Return the underlying `String`
     ///
     /// Note: this will not inspect binary data for being a valid `String`.
@@ -551,8 +838,15 @@ impl Data {
             DataValue::Json(_) => Some(self.to_string()),
             #[cfg(feature = "json")]
             DataValue::JsonLines(_) => Some(self.to_string()),
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(_) => Some(self.to_string()),
+            #[cfg(feature = "toml")]
+            DataValue::Toml(..) => Some(self.to_string()),
+            #[cfg(feature = "csv")]
+            DataValue::Csv(_) | DataValue::Tsv(_) => Some(self.to_string()),
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(data) => Some(data.to_owned()),
+            DataValue::Custom(data) => Some(data.render()),
         }
     }
 
@@ -565,8 +859,15 @@ impl Data {
             DataValue::Json(_) => Ok(self.to_string().into_bytes()),
             #[cfg(feature = "json")]
             DataValue::JsonLines(_) => Ok(self.to_string().into_bytes()),
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(_) => Ok(self.to_string().into_bytes()),
+            #[cfg(feature = "toml")]
+            DataValue::Toml(..) => Ok(self.to_string().into_bytes()),
+            #[cfg(feature = "csv")]
+            DataValue::Csv(_) | DataValue::Tsv(_) => Ok(self.to_string().into_bytes()),
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(data) => Ok(data.clone().into_bytes()),
+            DataValue::Custom(data) => Ok(data.render().into_bytes()),
         }
     }
 
@@ -606,8 +907,21 @@ impl Data {
             (DataValue::Json(inner), DataFormat::Json) => DataValue::Json(inner),
             #[cfg(feature = "json")]
             (DataValue::JsonLines(inner), DataFormat::JsonLines) => DataValue::JsonLines(inner),
+            #[cfg(feature = "yaml")]
+            (DataValue::Yaml(inner), DataFormat::Yaml) => DataValue::Yaml(inner),
+            #[cfg(feature = "toml")]
+            (DataValue::Toml(inner, datetimes), DataFormat::Toml) => {
+                DataValue::Toml(inner, datetimes)
+            }
+            #[cfg(feature = "csv")]
+            (DataValue::Csv(inner), DataFormat::Csv) => DataValue::Csv(inner),
+            #[cfg(feature = "csv")]
+            (DataValue::Tsv(inner), DataFormat::Tsv) => DataValue::Tsv(inner),
             #[cfg(feature = "term-svg")]
             (DataValue::TermSvg(inner), DataFormat::TermSvg) => DataValue::TermSvg(inner),
+            (DataValue::Custom(inner), DataFormat::Custom(custom)) if inner.format() == custom => {
+                DataValue::Custom(inner)
+            }
             (DataValue::Binary(inner), _) => {
                 let value = String::from_utf8(inner).map_err(|_err| "invalid UTF-8".to_owned())?;
                 Self::text(value).try_is(format)?.inner.value
@@ -623,14 +937,36 @@ impl Data {
                 let value = parse_jsonlines(&inner).map_err(|err| err.to_string())?;
                 DataValue::JsonLines(serde_json::Value::Array(value))
             }
+            #[cfg(feature = "yaml")]
+            (DataValue::Text(inner), DataFormat::Yaml) => {
+                let value = serde_norway::from_str::<serde_json::Value>(&inner)
+                    .map_err(|err| err.to_string())?;
+                DataValue::Yaml(value)
+            }
+            #[cfg(feature = "toml")]
+            (DataValue::Text(inner), DataFormat::Toml) => {
+                let (value, datetimes) = parse_toml(&inner).map_err(|err| err.to_string())?;
+                DataValue::Toml(value, datetimes)
+            }
+            #[cfg(feature = "csv")]
+            (DataValue::Text(inner), DataFormat::Csv) => {
+                let value = parse_records(&inner, b',').map_err(|err| err.to_string())?;
+                DataValue::Csv(value)
+            }
+            #[cfg(feature = "csv")]
+            (DataValue::Text(inner), DataFormat::Tsv) => {
+                let value = parse_records(&inner, b'\t').map_err(|err| err.to_string())?;
+                DataValue::Tsv(value)
+            }
             #[cfg(feature = "term-svg")]
             (DataValue::Text(inner), DataFormat::TermSvg) => DataValue::TermSvg(inner),
+            (DataValue::Text(inner), DataFormat::Custom(custom)) => {
+                DataValue::Custom(custom.parse(&inner)?)
+            }
             (value, DataFormat::Binary) => {
                 let remake = Self::with_value(value);
                 DataValue::Binary(remake.to_bytes().expect("error case handled"))
             }
-            // This variant is already covered unless structured data is enabled
-            #[cfg(feature = "structured-data")]
             (value, DataFormat::Text) => {
                 if let Some(str) = Self::with_value(value).render() {
                     DataValue::Text(str)
@@ -691,8 +1027,39 @@ impl Data {
             (DataValue::JsonLines(inner), DataFormat::Json) => DataValue::Json(inner),
             #[cfg(feature = "json")]
             (DataValue::Json(inner), DataFormat::JsonLines) => DataValue::JsonLines(inner),
+            #[cfg(feature = "yaml")]
+            (DataValue::Yaml(inner), DataFormat::Yaml) => DataValue::Yaml(inner),
+            #[cfg(all(feature = "json", feature = "yaml"))]
+            (DataValue::Json(inner), DataFormat::Yaml) => DataValue::Yaml(inner),
+            #[cfg(all(feature = "json", feature = "yaml"))]
+            (DataValue::Yaml(inner), DataFormat::Json) => DataValue::Json(inner),
+            #[cfg(feature = "toml")]
+            (DataValue::Toml(inner, datetimes), DataFormat::Toml) => {
+                DataValue::Toml(inner, datetimes)
+            }
+            #[cfg(all(feature = "json", feature = "toml"))]
+            (DataValue::Json(inner), DataFormat::Toml) => {
+                if is_toml_compatible(&inner) {
+                    DataValue::Toml(inner, Default::default())
+                } else {
+                    DataValue::Json(inner)
+                }
+            }
+            #[cfg(all(feature = "json", feature = "toml"))]
+            (DataValue::Toml(inner, _), DataFormat::Json) => DataValue::Json(inner),
+            #[cfg(feature = "csv")]
+            (DataValue::Csv(inner), DataFormat::Csv) => DataValue::Csv(inner),
+            #[cfg(feature = "csv")]
+            (DataValue::Tsv(inner), DataFormat::Tsv) => DataValue::Tsv(inner),
+            #[cfg(feature = "csv")]
+            (DataValue::Csv(inner), DataFormat::Tsv) => DataValue::Tsv(inner),
+            #[cfg(feature = "csv")]
+            (DataValue::Tsv(inner), DataFormat::Csv) => DataValue::Csv(inner),
             #[cfg(feature = "term-svg")]
             (DataValue::TermSvg(inner), DataFormat::TermSvg) => DataValue::TermSvg(inner),
+            (DataValue::Custom(inner), DataFormat::Custom(custom)) if inner.format() == custom => {
+                DataValue::Custom(inner)
+            }
             (DataValue::Binary(inner), _) => {
                 if is_binary(&inner) {
                     DataValue::Binary(inner)
@@ -732,16 +1099,53 @@ impl Data {
                     DataValue::Text(inner)
                 }
             }
+            #[cfg(feature = "yaml")]
+            (DataValue::Text(inner), DataFormat::Yaml) => {
+                if let Ok(yaml) = serde_norway::from_str::<serde_json::Value>(&inner) {
+                    DataValue::Yaml(yaml)
+                } else {
+                    DataValue::Text(inner)
+                }
+            }
+            #[cfg(feature = "toml")]
+            (DataValue::Text(inner), DataFormat::Toml) => {
+                if let Ok((toml, datetimes)) = parse_toml(&inner) {
+                    DataValue::Toml(toml, datetimes)
+                } else {
+                    DataValue::Text(inner)
+                }
+            }
+            #[cfg(feature = "csv")]
+            (DataValue::Text(inner), DataFormat::Csv) => {
+                if let Ok(records) = parse_records(&inner, b',') {
+                    DataValue::Csv(records)
+                } else {
+                    DataValue::Text(inner)
+                }
+            }
+            #[cfg(feature = "csv")]
+            (DataValue::Text(inner), DataFormat::Tsv) => {
+                if let Ok(records) = parse_records(&inner, b'\t') {
+                    DataValue::Tsv(records)
+                } else {
+                    DataValue::Text(inner)
+                }
+            }
             #[cfg(feature = "term-svg")]
             (DataValue::Text(inner), DataFormat::TermSvg) => {
                 DataValue::TermSvg(anstyle_svg::Term::new().render_svg(&inner))
             }
+            (DataValue::Text(inner), DataFormat::Custom(custom)) => {
+                if let Ok(value) = custom.parse(&inner) {
+                    DataValue::Custom(value)
+                } else {
+                    DataValue::Text(inner)
+                }
+            }
             (value, DataFormat::Binary) => {
                 let remake = Self::with_value(value);
                 DataValue::Binary(remake.to_bytes().expect("error case handled"))
             }
-            // This variant is already covered unless structured data is enabled
-            #[cfg(feature = "structured-data")]
             (value, DataFormat::Text) => {
                 let remake = Self::with_value(value);
                 if let Some(str) = remake.render() {
@@ -760,8 +1164,21 @@ impl Data {
             (value, DataFormat::JsonLines) => value,
             // reachable if more than one structured data format is enabled
             #[allow(unreachable_patterns)]
+            #[cfg(feature = "yaml")]
+            (value, DataFormat::Yaml) => value,
+            // reachable if more than one structured data format is enabled
+            #[allow(unreachable_patterns)]
+            #[cfg(feature = "toml")]
+            (value, DataFormat::Toml) => value,
+            // reachable if more than one structured data format is enabled
+            #[allow(unreachable_patterns)]
+            #[cfg(feature = "csv")]
+            (value, DataFormat::Csv | DataFormat::Tsv) => value,
+            // reachable if more than one structured data format is enabled
+            #[allow(unreachable_patterns)]
             #[cfg(feature = "term-svg")]
             (value, DataFormat::TermSvg) => value,
+            (value, DataFormat::Custom(_)) => value,
         };
         Self {
             inner: Box::new(DataInner {
@@ -787,8 +1204,17 @@ impl Data {
             DataValue::Json(_) => DataFormat::Json,
             #[cfg(feature = "json")]
             DataValue::JsonLines(_) => DataFormat::JsonLines,
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(_) => DataFormat::Yaml,
+            #[cfg(feature = "toml")]
+            DataValue::Toml(..) => DataFormat::Toml,
+            #[cfg(feature = "csv")]
+            DataValue::Csv(_) => DataFormat::Csv,
+            #[cfg(feature = "csv")]
+            DataValue::Tsv(_) => DataFormat::Tsv,
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(_) => DataFormat::TermSvg,
+            DataValue::Custom(data) => DataFormat::Custom(data.format()),
         }
     }
 
@@ -801,8 +1227,17 @@ impl Data {
             DataValue::Json(_) => DataFormat::Json,
             #[cfg(feature = "json")]
             DataValue::JsonLines(_) => DataFormat::JsonLines,
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(_) => DataFormat::Yaml,
+            #[cfg(feature = "toml")]
+            DataValue::Toml(..) => DataFormat::Toml,
+            #[cfg(feature = "csv")]
+            DataValue::Csv(_) => DataFormat::Csv,
+            #[cfg(feature = "csv")]
+            DataValue::Tsv(_) => DataFormat::Tsv,
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(_) => DataFormat::TermSvg,
+            DataValue::Custom(data) => DataFormat::Custom(data.format()),
         }
     }
 
@@ -813,6 +1248,32 @@ impl Data {
             .unwrap_or_else(|| self.intended_format())
     }
 
+    /// Apply a registered [`CustomFormat`] that matches the snapshot file's extension
+    pub(crate) fn with_custom_formats(mut self, formats: &[CustomFormat]) -> Self {
+        let Some(format) = self
+            .source()
+            .and_then(|source| source.as_path())
+            .and_then(|path| CustomFormat::infer(formats, path))
+        else {
+            return self;
+        };
+        match &mut self.inner.value {
+            DataValue::Error(DataError { intended, .. }) if *intended == DataFormat::Text => {
+                *intended = DataFormat::Custom(format);
+                self
+            }
+            DataValue::Binary(_) | DataValue::Text(_) => self.is(DataFormat::Custom(format)),
+            _ => self,
+        }
+    }
+
+    /// Render the underlying bytes for review
+    #[cfg(feature = "diff")]
+    pub(crate) fn hexdump(&self) -> Option<String> {
+        let bytes = self.to_bytes().ok()?;
+        Some(hexdump::encode(&bytes))
+    }
+
     pub(crate) fn relevant(&self) -> Option<&str> {
         match &self.inner.value {
             DataValue::Error(_) => None,
@@ -822,8 +1283,15 @@ impl Data {
             DataValue::Json(_) => None,
             #[cfg(feature = "json")]
             DataValue::JsonLines(_) => None,
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(_) => None,
+            #[cfg(feature = "toml")]
+            DataValue::Toml(..) => None,
+            #[cfg(feature = "csv")]
+            DataValue::Csv(_) | DataValue::Tsv(_) => None,
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(data) => term_svg_body(data),
+            DataValue::Custom(_) => None,
         }
     }
 }
@@ -835,17 +1303,31 @@ impl std::fmt::Display for Data {
             DataValue::Binary(data) => String::from_utf8_lossy(data).fmt(f),
             DataValue::Text(data) => data.fmt(f),
             #[cfg(feature = "json")]
-            DataValue::Json(data) => serde_json::to_string_pretty(data).unwrap().fmt(f),
+            DataValue::Json(data) => serde_json::to_string_pretty(&canonicalize_json(data))
+                .unwrap()
+                .fmt(f),
             #[cfg(feature = "json")]
             DataValue::JsonLines(data) => {
                 let array = data.as_array().expect("jsonlines is always an array");
                 for value in array {
-                    writeln!(f, "{}", serde_json::to_string(value).unwrap())?;
+                    let value = canonicalize_json(value);
+                    writeln!(f, "{}", serde_json::to_string(&value).unwrap())?;
                 }
                 Ok(())
             }
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(data) => serde_norway::to_string(&canonicalize_json(data))
+                .unwrap()
+                .fmt(f),
+            #[cfg(feature = "toml")]
+            DataValue::Toml(data, datetimes) => render_toml(data, datetimes).unwrap().fmt(f),
+            #[cfg(feature = "csv")]
+            DataValue::Csv(data) => render_records(data, b',').fmt(f),
+            #[cfg(feature = "csv")]
+            DataValue::Tsv(data) => render_records(data, b'\t').fmt(f),
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(data) => data.fmt(f),
+            DataValue::Custom(data) => data.render().fmt(f),
         }
     }
 }
@@ -860,6 +1342,14 @@ impl PartialEq for Data {
             (DataValue::Json(left), DataValue::Json(right)) => left == right,
             #[cfg(feature = "json")]
             (DataValue::JsonLines(left), DataValue::JsonLines(right)) => left == right,
+            #[cfg(feature = "yaml")]
+            (DataValue::Yaml(left), DataValue::Yaml(right)) => left == right,
+            #[cfg(feature = "toml")]
+            (DataValue::Toml(left, _), DataValue::Toml(right, _)) => left == right,
+            #[cfg(feature = "csv")]
+            (DataValue::Csv(left), DataValue::Csv(right)) => left == right,
+            #[cfg(feature = "csv")]
+            (DataValue::Tsv(left), DataValue::Tsv(right)) => left == right,
             #[cfg(feature = "term-svg")]
             (DataValue::TermSvg(left), DataValue::TermSvg(right)) => {
                 // HACK: avoid including `width` and `height` in the comparison
@@ -867,6 +1357,7 @@ impl PartialEq for Data {
                 let right = term_svg_body(right.as_str()).unwrap_or(right.as_str());
                 left == right
             }
+            (DataValue::Custom(left), DataValue::Custom(right)) => left == right,
             (_, _) => false,
         }
     }
@@ -884,6 +1375,14 @@ impl std::fmt::Display for DataError {
     }
 }
 
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+fn select_value(mut value: serde_json::Value, pointer: &str) -> Result<serde_json::Value, String> {
+    value
+        .pointer_mut(pointer)
+        .map(serde_json::Value::take)
+        .ok_or_else(|| format!("`{pointer}` not found"))
+}
+
 #[cfg(feature = "json")]
 fn parse_jsonlines(text: &str) -> Result<Vec<serde_json::Value>, serde_json::Error> {
     let mut lines = Vec::new();
@@ -898,6 +1397,177 @@ fn parse_jsonlines(text: &str) -> Result<Vec<serde_json::Value>, serde_json::Err
     Ok(lines)
 }
 
+/// [JSON Pointers](https://www.rfc-editor.org/rfc/rfc6901) to the datetimes in a toml document
+///
+/// Datetimes are compared and redacted as strings, so this is only to render them back as
+/// datetimes.
+#[cfg(feature = "toml")]
+pub(crate) type TomlDatetimes = std::collections::BTreeSet<String>;
+
+#[cfg(feature = "toml")]
+fn parse_toml(text: &str) -> Result<(serde_json::Value, TomlDatetimes), toml::de::Error> {
+    let mut value = toml::from_str::<serde_json::Value>(text)?;
+    let mut datetimes = TomlDatetimes::new();
+    flatten_toml_datetimes(&mut value, String::new(), &mut datetimes);
+    Ok((value, datetimes))
+}
+
+/// Datetimes are deserialized as a private struct, so replace them with strings
+#[cfg(feature = "toml")]
+fn flatten_toml_datetimes(
+    value: &mut serde_json::Value,
+    pointer: String,
+    datetimes: &mut TomlDatetimes,
+) {
+    const DATETIME_FIELD: &str = "$__toml_private_datetime";
+
+    match value {
+        serde_json::Value::Array(arr) => {
+            for (index, value) in arr.iter_mut().enumerate() {
+                flatten_toml_datetimes(value, format!("{pointer}/{index}"), datetimes);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            if obj.len() == 1 {
+                if let Some(serde_json::Value::String(datetime)) = obj.get_mut(DATETIME_FIELD) {
+                    *value = serde_json::Value::String(std::mem::take(datetime));
+                    datetimes.insert(pointer);
+                    return;
+                }
+            }
+            for (key, value) in obj.iter_mut() {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                flatten_toml_datetimes(value, format!("{pointer}/{key}"), datetimes);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Re-root `datetimes` for the table at `pointer`
+#[cfg(feature = "toml")]
+fn select_toml_datetimes(datetimes: TomlDatetimes, pointer: &str) -> TomlDatetimes {
+    datetimes
+        .into_iter()
+        .filter_map(|datetime| {
+            let relative = datetime.strip_prefix(pointer)?;
+            relative.starts_with('/').then(|| relative.to_owned())
+        })
+        .collect()
+}
+
+#[cfg(feature = "toml")]
+fn render_toml(
+    value: &serde_json::Value,
+    datetimes: &TomlDatetimes,
+) -> Result<String, toml::ser::Error> {
+    let mut value = toml::Value::try_from(canonicalize_json(value))?;
+    for pointer in datetimes {
+        let Some(value) = toml_pointer_mut(&mut value, pointer) else {
+            continue;
+        };
+        // Ones no longer holding a datetime, like after a redaction, stay strings
+        let datetime = value.as_str().and_then(|datetime| datetime.parse().ok());
+        if let Some(datetime) = datetime {
+            *value = toml::Value::Datetime(datetime);
+        }
+    }
+    toml::to_string_pretty(&value)
+}
+
+#[cfg(feature = "toml")]
+fn toml_pointer_mut<'v>(
+    mut value: &'v mut toml::Value,
+    pointer: &str,
+) -> Option<&'v mut toml::Value> {
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        value = match value {
+            toml::Value::Table(table) => table.get_mut(&token)?,
+            toml::Value::Array(arr) => arr.get_mut(token.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+#[cfg(all(feature = "json", feature = "toml"))]
+fn is_toml_compatible(value: &serde_json::Value) -> bool {
+    value.is_object() && toml::to_string(value).is_ok()
+}
+
+#[cfg(feature = "csv")]
+fn parse_records(text: &str, delimiter: u8) -> Result<Vec<Vec<String>>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut records = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        records.push(record.iter().map(|cell| cell.to_owned()).collect());
+    }
+    Ok(records)
+}
+
+/// Consistently order and format `value` for rendering
+///
+/// Object keys are sorted, independent of whether `serde_json/preserve_order` is enabled, and numbers
+/// are re-encoded, independent of whether `serde_json/arbitrary_precision` is enabled.
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+fn canonicalize_json(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Number(number) => {
+            let number = if let Some(n) = number.as_u64() {
+                serde_json::Number::from(n)
+            } else if let Some(n) = number.as_i64() {
+                serde_json::Number::from(n)
+            } else if let Some(n) = number.as_f64().and_then(serde_json::Number::from_f64) {
+                n
+            } else {
+                number.clone()
+            };
+            serde_json::Value::Number(number)
+        }
+        serde_json::Value::Array(array) => {
+            serde_json::Value::Array(array.iter().map(canonicalize_json).collect())
+        }
+        serde_json::Value::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), canonicalize_json(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Null | serde_json::Value::Bool(_) | serde_json::Value::String(_) => {
+            value.clone()
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+fn render_records(records: &[Vec<String>], delimiter: u8) -> String {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(Vec::new());
+    for record in records {
+        writer
+            .write_record(record)
+            .expect("writing to a `Vec` is infallible");
+    }
+    let bytes = writer
+        .into_inner()
+        .expect("writing to a `Vec` is infallible");
+    String::from_utf8(bytes).expect("records are always UTF-8")
+}
+
 #[cfg(feature = "term-svg")]
 fn term_svg_body(svg: &str) -> Option<&str> {
     let (_header, body, _footer) = split_term_svg(svg)?;
@@ -1098,4 +1768,110 @@ bad
         ];
         validate_cases(&cases, DataFormat::JsonLines);
     }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml() {
+        let cases = [
+            ("{}", true),
+            ("\"\"", true),
+            ("hello: world", true),
+            (
+                "
+items:
+- 1
+- two
+",
+                true,
+            ),
+            ("{bad", false),
+            ("key: [unclosed", false),
+        ];
+        validate_cases(&cases, DataFormat::Yaml);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml() {
+        let cases = [
+            ("", true),
+            ("bad", false),
+            ("\"\"", false),
+            ("hello = \"world\"", true),
+            (
+                "
+[package]
+name = \"foo\"
+authors = [\"bar\"]
+",
+                true,
+            ),
+            ("key = [unclosed", false),
+        ];
+        validate_cases(&cases, DataFormat::Toml);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv() {
+        let cases = [
+            ("", true),
+            ("good", true),
+            ("a,b\n1,2\n", true),
+            ("a,b\n1\n", true),
+            ("a,\"multi\nline\"\n", true),
+            ("a,\"quoted, comma\"", true),
+        ];
+        validate_cases(&cases, DataFormat::Csv);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn tsv() {
+        let cases = [
+            ("", true),
+            ("good", true),
+            ("a\tb\n1\t2\n", true),
+            ("a,b\t\"multi\nline\"\n", true),
+        ];
+        validate_cases(&cases, DataFormat::Tsv);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_multiline_cell() {
+        let actual = Data::text("name,notes\nfoo,\"one\ntwo\"\n").is(DataFormat::Csv);
+        let DataValue::Csv(records) = &actual.inner.value else {
+            panic!("expected csv: {actual:?}");
+        };
+        assert_eq!(
+            records,
+            &vec![
+                vec!["name".to_owned(), "notes".to_owned()],
+                vec!["foo".to_owned(), "one\ntwo".to_owned()],
+            ]
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_datetime() {
+        let actual = Data::text("released = 1979-05-27T07:32:00Z").is(DataFormat::Toml);
+        assert_eq!(
+            actual.render().unwrap(),
+            "released = 1979-05-27T07:32:00Z\n"
+        );
+
+        let quoted = Data::text("released = \"1979-05-27T07:32:00Z\"").is(DataFormat::Toml);
+        assert_eq!(
+            quoted.render().unwrap(),
+            "released = \"1979-05-27T07:32:00Z\"\n"
+        );
+
+        let nested = Data::text("[a]\nwhen = [1979-05-27, 1979-05-28]").is(DataFormat::Toml);
+        assert_eq!(
+            nested.select("/a").render().unwrap(),
+            "when = [\n    1979-05-27,\n    1979-05-28,\n]\n"
+        );
+    }
 }
@@ -0,0 +1,611 @@
+//! Proposed snapshot updates, see [`Assert::review`][crate::Assert::review]
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use super::Data;
+use super::DataSource;
+use super::Position;
+use super::source::DataSourceInner;
+use crate::utils::JsonParser;
+use crate::utils::encode_json_str;
+
+/// Suffix appended to a snapshot file for its [pending update][PendingSnapshot]
+///
+/// This is distinct from a plain `.new` so unrelated files are not mistaken for pending updates.
+pub const PENDING_SUFFIX: &str = ".snap.new";
+/// Suffix for the hidden file, next to a Rust source file, holding pending [`str!`][crate::str!] updates
+const PENDING_INLINE_SUFFIX: &str = ".pending-snap";
+
+/// Serialize updates to pending files from parallel tests
+static PENDING_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// A proposed update to a snapshot, recorded by [`Assert::review`][crate::Assert::review]
+///
+/// - For snapshot files, `actual` is written next to it with `.snap.new` appended, like
+///   `foo.stdout.txt.snap.new`
+/// - For [`str!`][crate::str!], `actual` is recorded as a line of JSON in a hidden
+///   `.<file>.pending-snap` next to the Rust source file
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use snapbox::data::PendingSnapshot;
+///
+/// let pending = PendingSnapshot::find("tests").unwrap();
+/// for update in &pending {
+///     println!("{update}");
+/// }
+/// PendingSnapshot::accept_all(pending).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingSnapshot {
+    inner: PendingInner,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PendingInner {
+    Path {
+        target: PathBuf,
+        pending: PathBuf,
+    },
+    Inline {
+        target: PathBuf,
+        pending: PathBuf,
+        record: InlineRecord,
+    },
+}
+
+impl PendingSnapshot {
+    /// Find all pending updates under `root`
+    ///
+    /// `target` and hidden directories are skipped.
+    pub fn find(root: impl AsRef<Path>) -> crate::assert::Result<Vec<Self>> {
+        let mut pending = Vec::new();
+        find_in(root.as_ref(), &mut pending)?;
+        pending.sort_by(|a, b| (a.target(), a.line()).cmp(&(b.target(), b.line())));
+        Ok(pending)
+    }
+
+    /// The snapshot file or Rust source file to be updated
+    pub fn target(&self) -> &Path {
+        match &self.inner {
+            PendingInner::Path { target, .. } | PendingInner::Inline { target, .. } => target,
+        }
+    }
+
+    /// The file the update is recorded in
+    pub fn pending_path(&self) -> &Path {
+        match &self.inner {
+            PendingInner::Path { pending, .. } | PendingInner::Inline { pending, .. } => pending,
+        }
+    }
+
+    /// Line of the [`str!`][crate::str!] to be updated
+    pub fn line(&self) -> Option<u32> {
+        match &self.inner {
+            PendingInner::Path { .. } => None,
+            PendingInner::Inline { record, .. } => Some(record.line),
+        }
+    }
+
+    /// The current snapshot
+    pub fn expected(&self) -> crate::assert::Result<Data> {
+        match &self.inner {
+            PendingInner::Path { target, .. } => {
+                if target.exists() {
+                    read_file(target)
+                } else {
+                    Ok(Data::new())
+                }
+            }
+            PendingInner::Inline { record, .. } => Ok(Data::text(record.expected.clone())),
+        }
+    }
+
+    /// The proposed snapshot
+    pub fn actual(&self) -> crate::assert::Result<Data> {
+        match &self.inner {
+            PendingInner::Path { pending, .. } => read_file(pending),
+            PendingInner::Inline { record, .. } => Ok(Data::text(record.actual.clone())),
+        }
+    }
+
+    /// Apply the update to the snapshot
+    ///
+    /// Accepting a [`str!`][crate::str!] update can move the `str!`s after it, invalidating other
+    /// `PendingSnapshot`s for the same source file.  Use [`PendingSnapshot::accept_all`] or
+    /// [`PendingSnapshot::find`] them again.
+    pub fn accept(self) -> crate::assert::Result<()> {
+        Self::accept_all([self])
+    }
+
+    /// Discard the update
+    pub fn reject(self) -> crate::assert::Result<()> {
+        Self::reject_all([self])
+    }
+
+    /// Apply each update to its snapshot
+    pub fn accept_all(pending: impl IntoIterator<Item = Self>) -> crate::assert::Result<()> {
+        let mut inline = BTreeMap::<PathBuf, (PathBuf, Vec<InlineRecord>)>::new();
+        for snapshot in pending {
+            match snapshot.inner {
+                PendingInner::Path { target, pending } => {
+                    std::fs::rename(&pending, &target).map_err(|e| {
+                        format!(
+                            "Failed to move {} to {}: {}",
+                            pending.display(),
+                            target.display(),
+                            e
+                        )
+                    })?;
+                }
+                PendingInner::Inline {
+                    target,
+                    pending,
+                    record,
+                } => {
+                    inline
+                        .entry(pending)
+                        .or_insert_with(|| (target, Vec::new()))
+                        .1
+                        .push(record);
+                }
+            }
+        }
+
+        for (pending, (target, accepted)) in inline {
+            let patches = accepted
+                .iter()
+                .map(|record| (record.position(&target), record.actual.as_str()))
+                .collect::<Vec<_>>();
+            let line_deltas = super::runtime::patch_inline(&target, &patches)
+                .map_err(|e| format!("Failed to update {}: {}", target.display(), e))?;
+            let _guard = lock();
+            update_records(&pending, |records| {
+                records.retain(|record| !accepted.iter().any(|a| a.is_at(record)));
+                for record in records.iter_mut() {
+                    let delta = accepted
+                        .iter()
+                        .zip(&line_deltas)
+                        .filter(|(accepted, _)| accepted.line < record.line)
+                        .map(|(_, delta)| delta)
+                        .sum::<isize>();
+                    record.line = record.line.saturating_add_signed(delta as i32);
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Discard each update
+    pub fn reject_all(pending: impl IntoIterator<Item = Self>) -> crate::assert::Result<()> {
+        let mut inline = BTreeMap::<PathBuf, Vec<InlineRecord>>::new();
+        for snapshot in pending {
+            match snapshot.inner {
+                PendingInner::Path { pending, .. } => {
+                    std::fs::remove_file(&pending)
+                        .map_err(|e| format!("Failed to remove {}: {}", pending.display(), e))?;
+                }
+                PendingInner::Inline {
+                    pending, record, ..
+                } => {
+                    inline.entry(pending).or_default().push(record);
+                }
+            }
+        }
+
+        let _guard = lock();
+        for (pending, rejected) in inline {
+            update_records(&pending, |records| {
+                records.retain(|record| !rejected.iter().any(|r| r.is_at(record)));
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for PendingSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.inner {
+            PendingInner::Path { target, .. } => crate::dir::display_relpath(target).fmt(f),
+            PendingInner::Inline { target, record, .. } => record.position(target).fmt(f),
+        }
+    }
+}
+
+/// Record `actual` as a pending update for `source`, returning where it was recorded
+pub(crate) fn write(actual: &Data, source: &DataSource) -> crate::assert::Result<PathBuf> {
+    match &source.inner {
+        DataSourceInner::Path(path) => {
+            let pending = pending_path(path);
+            if let Some(parent) = pending.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    format!(
+                        "Failed to create parent dir for {}: {}",
+                        pending.display(),
+                        e
+                    )
+                })?;
+            }
+            let bytes = actual.to_file_bytes(path)?;
+            std::fs::write(&pending, bytes)
+                .map_err(|e| format!("Failed to write {}: {}", pending.display(), e))?;
+            Ok(pending)
+        }
+        DataSourceInner::Inline(inline) => {
+            let actual = actual.render().expect("`actual` must be UTF-8");
+            let record = InlineRecord {
+                line: inline.position.line,
+                column: inline.position.column,
+                expected: inline.trimmed(),
+                actual,
+            };
+            let pending = inline_pending_path(&inline.position.file);
+            let _guard = lock();
+            update_records(&pending, |records| {
+                records.retain(|existing| !existing.is_at(&record));
+                records.push(record);
+                records.sort_by_key(|record| (record.line, record.column));
+            })?;
+            Ok(pending)
+        }
+    }
+}
+
+/// Remove a stale pending update for `source`, like when the snapshot now matches
+pub(crate) fn clear(source: &DataSource) -> crate::assert::Result<()> {
+    match &source.inner {
+        DataSourceInner::Path(path) => {
+            let pending = pending_path(path);
+            if pending.exists() {
+                std::fs::remove_file(&pending)
+                    .map_err(|e| format!("Failed to remove {}: {}", pending.display(), e))?;
+            }
+        }
+        DataSourceInner::Inline(inline) => {
+            let pending = inline_pending_path(&inline.position.file);
+            if pending.exists() {
+                let position = &inline.position;
+                let _guard = lock();
+                update_records(&pending, |records| {
+                    records.retain(|record| {
+                        (record.line, record.column) != (position.line, position.column)
+                    });
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn lock() -> std::sync::MutexGuard<'static, ()> {
+    PENDING_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn pending_path(path: &Path) -> PathBuf {
+    let mut pending = path.as_os_str().to_owned();
+    pending.push(PENDING_SUFFIX);
+    PathBuf::from(pending)
+}
+
+fn inline_pending_path(source: &Path) -> PathBuf {
+    let mut name = std::ffi::OsString::from(".");
+    name.push(source.file_name().unwrap_or_default());
+    name.push(PENDING_INLINE_SUFFIX);
+    source.with_file_name(name)
+}
+
+fn find_in(dir: &Path, pending: &mut Vec<PendingSnapshot>) -> crate::assert::Result<()> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        if is_dir {
+            if name != "target" && !name.starts_with('.') {
+                find_in(&path, pending)?;
+            }
+        } else if let Some(source) = name
+            .strip_prefix('.')
+            .and_then(|name| name.strip_suffix(PENDING_INLINE_SUFFIX))
+        {
+            let target = path.with_file_name(source);
+            for record in read_records(&path)? {
+                pending.push(PendingSnapshot {
+                    inner: PendingInner::Inline {
+                        target: target.clone(),
+                        pending: path.clone(),
+                        record,
+                    },
+                });
+            }
+        } else if let Some(target) = name.strip_suffix(PENDING_SUFFIX) {
+            pending.push(PendingSnapshot {
+                inner: PendingInner::Path {
+                    target: path.with_file_name(target),
+                    pending: path,
+                },
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Read a snapshot file as-is, without inferring its format
+fn read_file(path: &Path) -> crate::assert::Result<Data> {
+    let data =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(Data::binary(data)
+        .coerce_to(super::DataFormat::Text)
+        .with_path(path))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct InlineRecord {
+    line: u32,
+    column: u32,
+    expected: String,
+    actual: String,
+}
+
+impl InlineRecord {
+    fn position(&self, file: &Path) -> Position {
+        Position {
+            file: file.to_owned(),
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn is_at(&self, other: &Self) -> bool {
+        (self.line, self.column) == (other.line, other.column)
+    }
+
+    fn encode(&self) -> String {
+        let mut encoded = String::new();
+        encoded.push_str(&format!(
+            "{{\"line\":{},\"column\":{},\"expected\":",
+            self.line, self.column
+        ));
+        encode_json_str(&mut encoded, &self.expected);
+        encoded.push_str(",\"actual\":");
+        encode_json_str(&mut encoded, &self.actual);
+        encoded.push('}');
+        encoded
+    }
+
+    fn decode(line: &str) -> Option<Self> {
+        let mut parser = JsonParser::new(line);
+        let mut record = Self {
+            line: 0,
+            column: 0,
+            expected: String::new(),
+            actual: String::new(),
+        };
+        parser.eat('{')?;
+        loop {
+            let key = parser.str()?;
+            parser.eat(':')?;
+            match key.as_str() {
+                "line" => record.line = parser.number()?,
+                "column" => record.column = parser.number()?,
+                "expected" => record.expected = parser.str()?,
+                "actual" => record.actual = parser.str()?,
+                _ => return None,
+            }
+            if parser.eat(',').is_none() {
+                break;
+            }
+        }
+        parser.eat('}')?;
+        (parser.is_done() && record.line != 0).then_some(record)
+    }
+}
+
+fn read_records(path: &Path) -> crate::assert::Result<Vec<InlineRecord>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("Failed to read {}: {}", path.display(), err).into()),
+    };
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            InlineRecord::decode(line).ok_or_else(|| {
+                format!("{}:{}: invalid pending snapshot", path.display(), i + 1).into()
+            })
+        })
+        .collect()
+}
+
+fn update_records(
+    path: &Path,
+    op: impl FnOnce(&mut Vec<InlineRecord>),
+) -> crate::assert::Result<()> {
+    let mut records = read_records(path)?;
+    op(&mut records);
+    if records.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        }
+    } else {
+        let text = records
+            .iter()
+            .map(|record| format!("{}\n", record.encode()))
+            .collect::<String>();
+        std::fs::write(path, text)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn record_round_trip() {
+        let record = InlineRecord {
+            line: 12,
+            column: 5,
+            expected: "old \"value\"".to_owned(),
+            actual: "new\n\tvalue \\ \u{1b}[0m".to_owned(),
+        };
+        let encoded = record.encode();
+        assert_eq!(
+            encoded,
+            r#"{"line":12,"column":5,"expected":"old \"value\"","actual":"new\n\tvalue \\ \u001b[0m"}"#
+        );
+        assert_eq!(InlineRecord::decode(&encoded), Some(record));
+    }
+
+    #[test]
+    fn record_decode_errors() {
+        let cases = [
+            "",
+            "{}",
+            r#"{"line":1,"column":1,"actual":"a""#,
+            r#"{"line":1,"column":1,"actual":"a"} extra"#,
+            r#"{"line":1,"other":1}"#,
+        ];
+        for case in cases {
+            assert_eq!(InlineRecord::decode(case), None, "{case}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dir")]
+    fn path_accept_reject() {
+        let root = crate::dir::DirRoot::mutable_temp().unwrap();
+        let root_path = root.path().unwrap();
+        let accepted = root_path.join("accepted.txt");
+        let rejected = root_path.join("sub/rejected.txt");
+        std::fs::write(&accepted, "old").unwrap();
+
+        for path in [&accepted, &rejected] {
+            let source = DataSource::path(path);
+            write(&Data::text("new"), &source).unwrap();
+        }
+        // Not recorded by `write`
+        let unrelated = root_path.join("config.new");
+        std::fs::write(&unrelated, "fixture").unwrap();
+        std::fs::write(root_path.join("config"), "fixture").unwrap();
+        let pending = PendingSnapshot::find(root_path).unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].target(), accepted);
+        assert_eq!(pending[0].expected().unwrap().render().unwrap(), "old");
+        assert_eq!(pending[0].actual().unwrap().render().unwrap(), "new");
+        assert_eq!(pending[1].target(), rejected);
+        assert_eq!(pending[1].expected().unwrap().render().unwrap(), "");
+
+        let mut pending = pending.into_iter();
+        pending.next().unwrap().accept().unwrap();
+        pending.next().unwrap().reject().unwrap();
+        assert_eq!(std::fs::read_to_string(&accepted).unwrap(), "new");
+        assert!(!rejected.exists());
+        assert!(unrelated.exists());
+        assert_eq!(PendingSnapshot::find(root_path).unwrap(), vec![]);
+
+        root.close().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "dir")]
+    fn assert_review() {
+        let root = crate::dir::DirRoot::mutable_temp().unwrap();
+        let root_path = root.path().unwrap();
+        let target = root_path.join("output.txt");
+        std::fs::write(&target, "old").unwrap();
+        let expected = || Data::read_from(&target, None);
+        let assert = crate::Assert::new()
+            .action(crate::assert::Action::Verify)
+            .review(true);
+
+        assert
+            .try_eq(None, Data::text("new"), expected())
+            .unwrap_err();
+        let pending = PendingSnapshot::find(root_path).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].actual().unwrap().render().unwrap(), "new");
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "old");
+
+        assert.try_eq(None, Data::text("old"), expected()).unwrap();
+        assert_eq!(PendingSnapshot::find(root_path).unwrap(), vec![]);
+
+        root.close().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "dir")]
+    fn inline_accept_shifts_later_records() {
+        let root = crate::dir::DirRoot::mutable_temp().unwrap();
+        let root_path = root.path().unwrap();
+        let source_path = root_path.join("lib.rs");
+        let source = "\
+fn first() {
+    assert_data_eq!(actual, str![\"old\"]);
+}
+
+fn second() {
+    assert_data_eq!(actual, str![\"old\"]);
+}
+";
+        std::fs::write(&source_path, source).unwrap();
+        for line in [2, 6] {
+            let inline = crate::data::Inline {
+                position: Position {
+                    file: source_path.clone(),
+                    line,
+                    column: 29,
+                },
+                data: "old",
+            };
+            write(&Data::text("new\nlines"), &inline.into()).unwrap();
+        }
+
+        let mut pending = PendingSnapshot::find(root_path).unwrap();
+        assert_eq!(pending.len(), 2);
+        let second = pending.pop().unwrap();
+        assert_eq!(second.line(), Some(6));
+        let first = pending.pop().unwrap();
+        assert_eq!(first.expected().unwrap(), Data::text("old"));
+        first.accept().unwrap();
+
+        let pending = PendingSnapshot::find(root_path).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].line(), Some(9));
+        PendingSnapshot::accept_all(pending).unwrap();
+
+        let expected = "\
+fn first() {
+    assert_data_eq!(actual, str![[r#\"
+new
+lines
+\"#]]);
+}
+
+fn second() {
+    assert_data_eq!(actual, str![[r#\"
+new
+lines
+\"#]]);
+}
+";
+        assert_eq!(std::fs::read_to_string(&source_path).unwrap(), expected);
+        assert_eq!(PendingSnapshot::find(root_path).unwrap(), vec![]);
+
+        root.close().unwrap();
+    }
+}
@@ -0,0 +1,215 @@
+//! Track the snapshot files used by a test run, see [`unused_snapshots`]
+
+use std::collections::BTreeSet;
+use std::io::Write as _;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Value for [`DEFAULT_ACTION_ENV`][crate::assert::DEFAULT_ACTION_ENV] to record used snapshots
+const PRUNE_ENV_VALUE: &str = "prune";
+/// Where [`file![_]`][crate::file!] snapshots are generated, relative to the package root
+const SNAPSHOT_DIR: &str = "tests/snapshots";
+/// Log of snapshot files used, relative to [`SNAPSHOT_DIR`]
+const USED_LOG: &str = ".used";
+
+/// Snapshot files in `tests/snapshots/` that no test read while running with `SNAPSHOTS=prune`
+///
+/// Tests run with `SNAPSHOTS=prune` are verified as usual while each file they read from
+/// `tests/snapshots/` is recorded in `tests/snapshots/.used`.  Files not in that record belong to
+/// tests that were renamed or removed, or were not run, so only rely on this after running all
+/// tests successfully.
+///
+/// The record accumulates across runs; remove it with [`clear_used_snapshots`] to start fresh.
+///
+/// `package_root` is the directory tests are run from.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// for unused in snapbox::data::unused_snapshots(".").unwrap() {
+///     std::fs::remove_file(unused).unwrap();
+/// }
+/// ```
+pub fn unused_snapshots(package_root: impl AsRef<Path>) -> crate::assert::Result<Vec<PathBuf>> {
+    let snapshot_dir = package_root.as_ref().join(SNAPSHOT_DIR);
+    let log = snapshot_dir.join(USED_LOG);
+    let used = std::fs::read_to_string(&log).map_err(|e| {
+        format!(
+            "Failed to read {}, run tests with `{}={PRUNE_ENV_VALUE}` first: {}",
+            log.display(),
+            crate::assert::DEFAULT_ACTION_ENV,
+            e
+        )
+    })?;
+    let used = used
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| snapshot_dir.join(line))
+        .collect::<BTreeSet<_>>();
+
+    let mut unused = Vec::new();
+    find_unused(&snapshot_dir, &used, &log, &mut unused)?;
+    unused.sort();
+    Ok(unused)
+}
+
+/// Remove the record of snapshot files used, see [`unused_snapshots`]
+pub fn clear_used_snapshots(package_root: impl AsRef<Path>) -> crate::assert::Result<()> {
+    let log = package_root.as_ref().join(SNAPSHOT_DIR).join(USED_LOG);
+    match std::fs::remove_file(&log) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(format!("Failed to remove {}: {}", log.display(), err).into()),
+    }
+}
+
+/// Record `path` as used when running with `SNAPSHOTS=prune`
+pub(crate) fn record(path: &Path) {
+    static ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    let enabled = *ENABLED.get_or_init(|| {
+        std::env::var_os(crate::assert::DEFAULT_ACTION_ENV).as_deref()
+            == Some(PRUNE_ENV_VALUE.as_ref())
+    });
+    if !enabled {
+        return;
+    }
+    let Ok(package_root) = std::env::current_dir() else {
+        return;
+    };
+    if let Err(_err) = record_in(&package_root, path) {
+        crate::debug!("Failed to record {} as used: {}", path.display(), _err);
+    }
+}
+
+fn record_in(package_root: &Path, path: &Path) -> std::io::Result<()> {
+    static RECORDED: std::sync::Mutex<BTreeSet<PathBuf>> = std::sync::Mutex::new(BTreeSet::new());
+
+    let snapshot_dir = package_root.join(SNAPSHOT_DIR);
+    let path = normalize(&package_root.join(path));
+    let Ok(relpath) = path.strip_prefix(&snapshot_dir) else {
+        return Ok(());
+    };
+    let mut recorded = RECORDED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !recorded.insert(path.clone()) {
+        return Ok(());
+    }
+
+    let line = relpath
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    std::fs::create_dir_all(&snapshot_dir)?;
+    let mut log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(snapshot_dir.join(USED_LOG))?;
+    writeln!(log, "{line}")
+}
+
+fn find_unused(
+    dir: &Path,
+    used: &BTreeSet<PathBuf>,
+    log: &Path,
+    unused: &mut Vec<PathBuf>,
+) -> crate::assert::Result<()> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        let path = entry.path();
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        if is_dir {
+            find_unused(&path, used, log, unused)?;
+        } else if path != log && !used.contains(&path) && !is_pending(&path) {
+            unused.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Whether `path` is a [pending update][crate::data::PendingSnapshot] rather than a snapshot
+fn is_pending(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(super::pending::PENDING_SUFFIX))
+}
+
+/// Lexically resolve `.` and `..` so paths can be compared
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_paths() {
+        let cases = [
+            ("/a/./b/../c", "/a/c"),
+            ("/a/tests/snapshots/x.txt", "/a/tests/snapshots/x.txt"),
+            (
+                "/a/tests/../tests/./snapshots/x.txt",
+                "/a/tests/snapshots/x.txt",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize(Path::new(input)), Path::new(expected), "{input}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dir")]
+    fn record_and_list_unused() {
+        let root = crate::dir::DirRoot::mutable_temp().unwrap();
+        let package_root = root.path().unwrap();
+        let snapshot_dir = package_root.join(SNAPSHOT_DIR);
+        std::fs::create_dir_all(snapshot_dir.join("nested")).unwrap();
+        for name in [
+            "used.txt",
+            "used@1.txt",
+            "unused.txt",
+            "nested/used.txt",
+            "used.txt.snap.new",
+        ] {
+            std::fs::write(snapshot_dir.join(name), "").unwrap();
+        }
+        std::fs::write(package_root.join("outside.txt"), "").unwrap();
+
+        assert!(unused_snapshots(package_root).is_err());
+        record_in(package_root, Path::new("tests/snapshots/used.txt")).unwrap();
+        record_in(package_root, Path::new("tests/snapshots/used.txt")).unwrap();
+        record_in(package_root, &snapshot_dir.join("used@1.txt")).unwrap();
+        record_in(
+            package_root,
+            &package_root.join("tests/./snapshots/nested/used.txt"),
+        )
+        .unwrap();
+        record_in(package_root, &package_root.join("outside.txt")).unwrap();
+
+        let log = std::fs::read_to_string(snapshot_dir.join(USED_LOG)).unwrap();
+        assert_eq!(log, "used.txt\nused@1.txt\nnested/used.txt\n");
+        let unused = unused_snapshots(package_root).unwrap();
+        assert_eq!(unused, vec![snapshot_dir.join("unused.txt")]);
+
+        clear_used_snapshots(package_root).unwrap();
+        assert!(unused_snapshots(package_root).is_err());
+        clear_used_snapshots(package_root).unwrap();
+
+        root.close().unwrap();
+    }
+}
@@ -56,6 +56,30 @@ impl Runtime {
     }
 }
 
+/// Replace the `str!` literals in `file` with `actual`, outside of a test run
+///
+/// Returns the change in the number of lines for each patch
+pub(crate) fn patch_inline(
+    file: &std::path::Path,
+    patches: &[(Position, &str)],
+) -> std::io::Result<Vec<isize>> {
+    let original_text = std::fs::read_to_string(file)?;
+    let mut patchwork = Patchwork::new(original_text.clone());
+    let mut line_deltas = Vec::with_capacity(patches.len());
+    for (position, actual) in patches {
+        let span = Span::try_from_pos(position, &original_text)
+            .ok_or_else(|| std::io::Error::other(format!("no `str!` found at {position}")))?;
+        let original = &original_text[span.literal_range.clone()];
+        let patch = format_patch(actual);
+        let line_delta =
+            patch.matches('\n').count() as isize - original.matches('\n').count() as isize;
+        patchwork.patch(span.literal_range, &patch)?;
+        line_deltas.push(line_delta);
+    }
+    std::fs::write(file, &patchwork.text)?;
+    Ok(line_deltas)
+}
+
 struct SourceFileRuntime {
     path: std::path::PathBuf,
     original_text: String,
@@ -192,6 +216,10 @@ struct Span {
 
 impl Span {
     fn from_pos(pos: &Position, file: &str) -> Span {
+        Self::try_from_pos(pos, file).expect("Couldn't find `str!` invocation")
+    }
+
+    fn try_from_pos(pos: &Position, file: &str) -> Option<Span> {
         let mut target_line = None;
         let mut line_start = 0;
         for (i, line) in crate::utils::LinesWithTerminator::new(file).enumerate() {
@@ -213,8 +241,7 @@ impl Span {
                     .skip_while(|&(_, c)| c.is_whitespace())
                     .skip(1) // [({
                     .skip_while(|&(_, c)| c.is_whitespace())
-                    .next()
-                    .expect("Failed to parse macro invocation")
+                    .next()?
                     .0;
 
                 let literal_start = line_start + byte_offset;
@@ -223,17 +250,16 @@ impl Span {
             }
             line_start += line.len();
         }
-        let literal_start = target_line.unwrap();
+        let literal_start = target_line?;
 
         let lit_to_eof = &file[literal_start..];
         let lit_to_eof_trimmed = lit_to_eof.trim_start();
 
         let literal_start = literal_start + (lit_to_eof.len() - lit_to_eof_trimmed.len());
 
-        let literal_len =
-            locate_end(lit_to_eof_trimmed).expect("Couldn't find closing delimiter for `expect!`.");
+        let literal_len = locate_end(lit_to_eof_trimmed)?;
         let literal_range = literal_start..literal_start + literal_len;
-        Span { literal_range }
+        Some(Span { literal_range })
     }
 }
 
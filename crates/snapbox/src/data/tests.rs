@@ -91,6 +91,42 @@ fn json_to_bytes_render() {
     assert_eq!(bytes, rendered);
 }
 
+#[test]
+#[cfg(feature = "json")]
+fn canonicalize_json_sorts_keys() {
+    // Only in insertion order with `serde_json/preserve_order`, which CI tests separately
+    let mut object = serde_json::Map::new();
+    object.insert("zebra".to_owned(), json!(1));
+    object.insert("apple".to_owned(), json!({"b": 2.5, "a": -3}));
+    let value = canonicalize_json(&serde_json::Value::Object(object));
+    let keys = value.as_object().unwrap().keys().collect::<Vec<_>>();
+    assert_eq!(keys, ["apple", "zebra"]);
+    let keys = value["apple"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["a", "b"]);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_render_sorts_keys() {
+    let mut object = serde_json::Map::new();
+    object.insert("zebra".to_owned(), json!(1));
+    object.insert("apple".to_owned(), json!({"b": 2.5, "a": -3}));
+    let d = Data::json(serde_json::Value::Object(object));
+    let expected = r#"{
+  "apple": {
+    "a": -3,
+    "b": 2.5
+  },
+  "zebra": 1
+}"#;
+    assert_eq!(d.render().unwrap(), expected);
+    assert_eq!(String::from_utf8(d.to_bytes().unwrap()).unwrap(), expected);
+}
+
 // Tests for checking all types are coercible to each other and
 // for when the coercion should fail
 #[test]
@@ -280,3 +316,289 @@ world",
         assert_eq!(expected, actual);
     }
 }
+
+#[test]
+#[cfg(feature = "dir")]
+fn hexdump_write_read_round_trip() {
+    let root = crate::dir::DirRoot::mutable_temp().unwrap();
+    let path = root.path().unwrap().join("data.hex");
+    let data = Data::binary(vec![0x00, 0x9f, 0x92, 0x96]);
+
+    data.write_to_path(&path).unwrap();
+    let dump = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        dump,
+        "00000000  00 9f 92 96                                       |....|\n"
+    );
+
+    let read = Data::read_from(&path, None);
+    assert_eq!(read.format(), DataFormat::Binary);
+    assert_eq!(read, data);
+
+    root.close().unwrap();
+}
+
+struct Words;
+
+impl SnapshotFormat for Words {
+    type Value = Vec<String>;
+
+    fn name(&self) -> &'static str {
+        "words"
+    }
+
+    fn ext(&self) -> &'static str {
+        "words"
+    }
+
+    fn parse(&self, raw: &str) -> crate::assert::Result<Self::Value> {
+        if raw.contains('!') {
+            return Err("unexpected `!`".into());
+        }
+        Ok(raw.split_whitespace().map(|w| w.to_owned()).collect())
+    }
+
+    fn render(&self, value: &Self::Value) -> String {
+        value.iter().map(|w| format!("{w}\n")).collect()
+    }
+
+    fn normalize_to_expected(
+        &self,
+        actual: Self::Value,
+        expected: &Self::Value,
+        redactions: &crate::Redactions,
+    ) -> Self::Value {
+        actual
+            .into_iter()
+            .zip(expected)
+            .map(|(actual, expected)| {
+                if redactions.redact(&actual) == *expected {
+                    expected.clone()
+                } else {
+                    actual
+                }
+            })
+            .collect()
+    }
+}
+
+static WORDS: Words = Words;
+
+#[test]
+fn custom_format_is() {
+    let format = DataFormat::Custom(CustomFormat::new(&WORDS));
+
+    let data = Data::text("hello   world").is(format);
+    assert_eq!(data.format(), format);
+    assert_eq!(data.render().unwrap(), "hello\nworld\n");
+    assert_eq!(data, Data::text("hello\nworld").is(format));
+    assert_ne!(data, Data::text("hello world").is(DataFormat::Text));
+
+    let data = Data::text("hello world!").is(format);
+    assert_eq!(data.format(), DataFormat::Error);
+    assert_eq!(data.intended_format(), format);
+
+    let data = Data::text("hello world!").coerce_to(format);
+    assert_eq!(data.format(), DataFormat::Text);
+}
+
+/// Same name as [`Words`] but a different [`SnapshotFormat::Value`]
+struct Sentence;
+
+impl SnapshotFormat for Sentence {
+    type Value = String;
+
+    fn name(&self) -> &'static str {
+        "words"
+    }
+
+    fn ext(&self) -> &'static str {
+        "sentence"
+    }
+
+    fn parse(&self, raw: &str) -> crate::assert::Result<Self::Value> {
+        Ok(raw.trim().to_owned())
+    }
+
+    fn render(&self, value: &Self::Value) -> String {
+        format!("{value}\n")
+    }
+}
+
+static SENTENCE: Sentence = Sentence;
+
+#[test]
+fn custom_format_identity() {
+    let words = CustomFormat::new(&WORDS);
+    let sentence = CustomFormat::new(&SENTENCE);
+    assert_eq!(words, CustomFormat::new(&WORDS));
+    assert_ne!(words, sentence);
+
+    let data = Data::text("hello world").is(DataFormat::Custom(words));
+    let coerced = data.clone().coerce_to(DataFormat::Custom(sentence));
+    assert_eq!(coerced.format(), DataFormat::Custom(words));
+    assert_ne!(
+        data,
+        Data::text("hello world").is(DataFormat::Custom(sentence))
+    );
+}
+
+/// Splits on a configurable separator
+struct Split(char);
+
+impl SnapshotFormat for Split {
+    type Value = Vec<String>;
+
+    fn name(&self) -> &'static str {
+        "split"
+    }
+
+    fn ext(&self) -> &'static str {
+        "split"
+    }
+
+    fn parse(&self, raw: &str) -> crate::assert::Result<Self::Value> {
+        Ok(raw.split(self.0).map(|w| w.to_owned()).collect())
+    }
+
+    fn render(&self, value: &Self::Value) -> String {
+        value.join(&self.0.to_string())
+    }
+}
+
+static COMMAS: Split = Split(',');
+static SEMICOLONS: Split = Split(';');
+
+#[test]
+fn custom_format_identity_by_instance() {
+    let commas = CustomFormat::new(&COMMAS);
+    let semicolons = CustomFormat::new(&SEMICOLONS);
+    assert_eq!(commas, CustomFormat::new(&COMMAS));
+    assert_ne!(commas, semicolons);
+
+    assert_ne!(
+        Data::text("a,b").is(DataFormat::Custom(commas)),
+        Data::text("a;b").is(DataFormat::Custom(semicolons))
+    );
+}
+
+#[test]
+fn custom_format_normalize_to_expected() {
+    let format = DataFormat::Custom(CustomFormat::new(&WORDS));
+    let mut redactions = crate::Redactions::new();
+    redactions.insert("[USER]", "alice").unwrap();
+    let assert = crate::Assert::new()
+        .action(crate::assert::Action::Verify)
+        .redact_with(redactions);
+
+    let (actual, expected) = assert.normalize(
+        Data::text("hello alice"),
+        Data::text("hello [USER]").is(format),
+    );
+    assert_eq!(actual, expected);
+
+    let (actual, expected) = assert.normalize(
+        Data::text("hello bob"),
+        Data::text("hello [USER]").is(format),
+    );
+    assert_ne!(actual, expected);
+}
+
+#[test]
+#[cfg(feature = "dir")]
+fn custom_format_registered_ext() {
+    let root = crate::dir::DirRoot::mutable_temp().unwrap();
+    let path = root.path().unwrap().join("greeting.words");
+    std::fs::write(&path, "hello\nworld\n").unwrap();
+    let format = CustomFormat::new(&WORDS);
+
+    let assert = crate::Assert::new().action(crate::assert::Action::Verify);
+    let (actual, expected) =
+        assert.normalize(Data::text("hello world"), Data::read_from(&path, None));
+    assert_eq!(expected.format(), DataFormat::Text);
+    assert_ne!(actual, expected);
+
+    let assert = assert.register_format(format);
+    let (actual, expected) =
+        assert.normalize(Data::text("hello world"), Data::read_from(&path, None));
+    assert_eq!(expected.format(), DataFormat::Custom(format));
+    assert_eq!(actual, expected);
+
+    let missing = root.path().unwrap().join("missing.words");
+    let (actual, expected) =
+        assert.normalize(Data::text("hello world"), Data::read_from(&missing, None));
+    assert_eq!(expected.intended_format(), DataFormat::Custom(format));
+    assert_eq!(actual.format(), DataFormat::Custom(format));
+
+    root.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_select() {
+    let data = Data::json(json!({"items": [{"meta": {"id": 5}, "body": "..."}]}));
+
+    let actual = data.clone().select("/items/0/meta");
+    assert_eq!(actual, Data::json(json!({"id": 5})));
+
+    let actual = data.clone().select("");
+    assert_eq!(actual, data);
+
+    let actual = data.select("/items/1/meta");
+    assert_eq!(
+        actual,
+        Data::error("`/items/1/meta` not found", DataFormat::Json)
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn jsonlines_select() {
+    let data = Data::jsonlines(vec![json!({"id": 1}), json!({"id": 2})]);
+
+    let actual = data.clone().select("/1");
+    assert_eq!(actual, Data::json(json!({"id": 2})));
+
+    let actual = data.select("/2");
+    assert_eq!(actual, Data::error("`/2` not found", DataFormat::Json));
+}
+
+#[test]
+#[cfg(all(feature = "json", feature = "dir"))]
+fn select_drops_source() {
+    let root = crate::dir::DirRoot::mutable_temp().unwrap();
+    let path = root.path().unwrap().join("data.json");
+    std::fs::write(&path, r#"{"items": [1]}"#).unwrap();
+
+    let data = Data::read_from(&path, Some(DataFormat::Json));
+    assert!(data.source().is_some());
+    let actual = data.select("/items");
+    assert_eq!(actual.source(), None);
+
+    root.close().unwrap();
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn toml_select() {
+    let data = Data::text("[a]\nwhen = 1979-05-27T07:32:00Z").is(DataFormat::Toml);
+
+    let actual = data.clone().select("/a");
+    assert_eq!(actual.render().unwrap(), "when = 1979-05-27T07:32:00Z\n");
+
+    let actual = data.select("/a/when");
+    assert_eq!(
+        actual,
+        Data::error("`/a/when` is not a table", DataFormat::Toml)
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn text_select() {
+    let actual = Data::text("{}").select("/items");
+    assert_eq!(
+        actual,
+        Data::error("cannot select `/items` from Text", DataFormat::Text)
+    );
+}
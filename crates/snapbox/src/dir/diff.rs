@@ -303,6 +303,27 @@ impl PathDiff {
             } => actual_content.write_to(expected_content.source().unwrap()),
         }
     }
+
+    /// Record `actual` as a [pending update][crate::data::PendingSnapshot] to `expected`
+    ///
+    /// Only content mismatches can be reviewed.
+    pub fn write_pending(&self) -> Result<(), crate::assert::Error> {
+        match self {
+            // Not passing the error up because users most likely want to treat a processing error
+            // differently than a review error
+            Self::Failure(_err) => Ok(()),
+            Self::TypeMismatch { .. } | Self::LinkMismatch { .. } => {
+                Err("only content changes can be reviewed".into())
+            }
+            Self::ContentMismatch {
+                expected_path: _,
+                actual_path: _,
+                expected_content,
+                actual_content,
+            } => crate::data::pending::write(actual_content, expected_content.source().unwrap())
+                .map(|_pending| ()),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
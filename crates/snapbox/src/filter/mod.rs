@@ -5,6 +5,7 @@
 //! - Focusing snapshots on the characteristics of the data being tested
 
 mod pattern;
+mod preset;
 mod redactions;
 #[cfg(test)]
 mod test;
@@ -17,6 +18,7 @@ use crate::Data;
 use crate::data::DataValue;
 
 pub use pattern::NormalizeToExpected;
+pub use preset::Preset;
 pub use redactions::RedactedValue;
 pub use redactions::Redactions;
 
@@ -48,11 +50,36 @@ impl Filter for FilterNewlines {
                 normalize_json_string(&mut value, &normalize_lines);
                 DataValue::JsonLines(value)
             }
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(value) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &normalize_lines);
+                DataValue::Yaml(value)
+            }
+            #[cfg(feature = "toml")]
+            DataValue::Toml(value, datetimes) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &normalize_lines);
+                DataValue::Toml(value, datetimes)
+            }
+            #[cfg(feature = "csv")]
+            DataValue::Csv(records) => {
+                let mut records = records;
+                normalize_records(&mut records, &normalize_lines);
+                DataValue::Csv(records)
+            }
+            #[cfg(feature = "csv")]
+            DataValue::Tsv(records) => {
+                let mut records = records;
+                normalize_records(&mut records, &normalize_lines);
+                DataValue::Tsv(records)
+            }
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(text) => {
                 let lines = normalize_lines(&text);
                 DataValue::TermSvg(lines)
             }
+            DataValue::Custom(value) => DataValue::Custom(value),
         };
         Data {
             inner: Box::new(crate::data::DataInner {
@@ -97,11 +124,36 @@ impl Filter for FilterPaths {
                 normalize_json_string(&mut value, &normalize_paths);
                 DataValue::JsonLines(value)
             }
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(value) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &normalize_paths);
+                DataValue::Yaml(value)
+            }
+            #[cfg(feature = "toml")]
+            DataValue::Toml(value, datetimes) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &normalize_paths);
+                DataValue::Toml(value, datetimes)
+            }
+            #[cfg(feature = "csv")]
+            DataValue::Csv(records) => {
+                let mut records = records;
+                normalize_records(&mut records, &normalize_paths);
+                DataValue::Csv(records)
+            }
+            #[cfg(feature = "csv")]
+            DataValue::Tsv(records) => {
+                let mut records = records;
+                normalize_records(&mut records, &normalize_paths);
+                DataValue::Tsv(records)
+            }
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(text) => {
                 let lines = normalize_paths(&text);
                 DataValue::TermSvg(lines)
             }
+            DataValue::Custom(value) => DataValue::Custom(value),
         };
         Data {
             inner: Box::new(crate::data::DataInner {
@@ -127,8 +179,250 @@ fn normalize_paths_chars(data: impl Iterator<Item = char>) -> impl Iterator<Item
     data.map(|c| if c == '\\' { '/' } else { c })
 }
 
+/// Remove [ANSI escape codes](https://en.wikipedia.org/wiki/ANSI_escape_code), like colors
+pub struct FilterStripAnsi;
+impl Filter for FilterStripAnsi {
+    fn filter(&self, data: Data) -> Data {
+        let source = data.inner.source;
+        let filters = data.inner.filters;
+        let inner = match data.inner.value {
+            DataValue::Error(err) => DataValue::Error(err),
+            DataValue::Binary(bin) => DataValue::Binary(bin),
+            DataValue::Text(text) => {
+                let lines = strip_ansi(&text);
+                DataValue::Text(lines)
+            }
+            #[cfg(feature = "json")]
+            DataValue::Json(value) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &strip_ansi);
+                DataValue::Json(value)
+            }
+            #[cfg(feature = "json")]
+            DataValue::JsonLines(value) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &strip_ansi);
+                DataValue::JsonLines(value)
+            }
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(value) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &strip_ansi);
+                DataValue::Yaml(value)
+            }
+            #[cfg(feature = "toml")]
+            DataValue::Toml(value, datetimes) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &strip_ansi);
+                DataValue::Toml(value, datetimes)
+            }
+            #[cfg(feature = "csv")]
+            DataValue::Csv(records) => {
+                let mut records = records;
+                normalize_records(&mut records, &strip_ansi);
+                DataValue::Csv(records)
+            }
+            #[cfg(feature = "csv")]
+            DataValue::Tsv(records) => {
+                let mut records = records;
+                normalize_records(&mut records, &strip_ansi);
+                DataValue::Tsv(records)
+            }
+            // Escape codes are what is being rendered
+            #[cfg(feature = "term-svg")]
+            DataValue::TermSvg(text) => DataValue::TermSvg(text),
+            DataValue::Custom(value) => DataValue::Custom(value),
+        };
+        Data {
+            inner: Box::new(crate::data::DataInner {
+                value: inner,
+                source,
+                filters,
+            }),
+        }
+    }
+}
+
+/// Remove [ANSI escape codes](https://en.wikipedia.org/wiki/ANSI_escape_code)
+///
+/// This covers CSI sequences (including SGR styling), OSC sequences (like hyperlinks), character
+/// set selection, and other two-character escapes.
+pub fn strip_ansi(data: &str) -> String {
+    const ESC: char = '\x1b';
+    const BEL: char = '\x07';
+
+    let mut stripped = String::with_capacity(data.len());
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != ESC {
+            stripped.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameter and intermediate bytes, ended by a final byte
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ended by BEL or ST (`ESC \`)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == BEL {
+                        break;
+                    }
+                    if c == ESC && chars.next_if_eq(&'\\').is_some() {
+                        break;
+                    }
+                }
+            }
+            // nF: intermediate bytes, ended by a final byte
+            Some('\x20'..='\x2f') => {
+                for c in chars.by_ref() {
+                    if ('\x30'..='\x7e').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(_) | None => {}
+        }
+    }
+    stripped
+}
+
+/// Trim whitespace at the end of each line before comparing
+pub struct FilterTrimTrailingWhitespace;
+impl Filter for FilterTrimTrailingWhitespace {
+    fn filter(&self, data: Data) -> Data {
+        filter_text(data, &trim_trailing_whitespace)
+    }
+}
+
+/// Remove whitespace at the end of each line
+pub fn trim_trailing_whitespace(data: &str) -> String {
+    let mut trimmed = String::with_capacity(data.len());
+    for line in data.split_inclusive('\n') {
+        let (content, newline) = match line.strip_suffix('\n') {
+            Some(content) => (content, "\n"),
+            None => (line, ""),
+        };
+        trimmed.push_str(content.trim_end());
+        trimmed.push_str(newline);
+    }
+    trimmed
+}
+
+/// Treat runs of spaces and tabs as a single space
+pub struct FilterCollapseSpaces;
+impl Filter for FilterCollapseSpaces {
+    fn filter(&self, data: Data) -> Data {
+        filter_text(data, &collapse_spaces)
+    }
+}
+
+/// Replace each run of spaces and tabs with a single space
+pub fn collapse_spaces(data: &str) -> String {
+    let mut collapsed = String::with_capacity(data.len());
+    let mut in_run = false;
+    for c in data.chars() {
+        if c == ' ' || c == '\t' {
+            if !in_run {
+                collapsed.push(' ');
+            }
+            in_run = true;
+        } else {
+            collapsed.push(c);
+            in_run = false;
+        }
+    }
+    collapsed
+}
+
+/// Remove lines that are empty or only contain whitespace before comparing
+pub struct FilterRemoveBlankLines;
+impl Filter for FilterRemoveBlankLines {
+    fn filter(&self, data: Data) -> Data {
+        filter_text(data, &remove_blank_lines)
+    }
+}
+
+/// Remove lines that are empty or only contain whitespace
+pub fn remove_blank_lines(data: &str) -> String {
+    data.split_inclusive('\n')
+        .filter(|line| !line.trim().is_empty())
+        .collect()
+}
+
+/// Apply `op` to all strings in `data`
+///
+/// Unlike the other filters, rendered terminal output is left as-is.
+fn filter_text(data: Data, op: &dyn Fn(&str) -> String) -> Data {
+    let source = data.inner.source;
+    let filters = data.inner.filters;
+    let inner = match data.inner.value {
+        DataValue::Error(err) => DataValue::Error(err),
+        DataValue::Binary(bin) => DataValue::Binary(bin),
+        DataValue::Text(text) => DataValue::Text(op(&text)),
+        #[cfg(feature = "json")]
+        DataValue::Json(value) => {
+            let mut value = value;
+            normalize_json_string(&mut value, op);
+            DataValue::Json(value)
+        }
+        #[cfg(feature = "json")]
+        DataValue::JsonLines(value) => {
+            let mut value = value;
+            normalize_json_string(&mut value, op);
+            DataValue::JsonLines(value)
+        }
+        #[cfg(feature = "yaml")]
+        DataValue::Yaml(value) => {
+            let mut value = value;
+            normalize_json_string(&mut value, op);
+            DataValue::Yaml(value)
+        }
+        #[cfg(feature = "toml")]
+        DataValue::Toml(value, datetimes) => {
+            let mut value = value;
+            normalize_json_string(&mut value, op);
+            DataValue::Toml(value, datetimes)
+        }
+        #[cfg(feature = "csv")]
+        DataValue::Csv(records) => {
+            let mut records = records;
+            normalize_records(&mut records, op);
+            DataValue::Csv(records)
+        }
+        #[cfg(feature = "csv")]
+        DataValue::Tsv(records) => {
+            let mut records = records;
+            normalize_records(&mut records, op);
+            DataValue::Tsv(records)
+        }
+        #[cfg(feature = "term-svg")]
+        DataValue::TermSvg(text) => DataValue::TermSvg(text),
+        DataValue::Custom(value) => DataValue::Custom(value),
+    };
+    Data {
+        inner: Box::new(crate::data::DataInner {
+            value: inner,
+            source,
+            filters,
+        }),
+    }
+}
+
 struct NormalizeRedactions<'r> {
     redactions: &'r Redactions,
+    backrefs: std::cell::RefCell<redactions::Backrefs>,
+}
+impl NormalizeRedactions<'_> {
+    fn redact(&self, input: &str) -> String {
+        self.redactions
+            .redact_numbered(input, &mut self.backrefs.borrow_mut())
+    }
 }
 impl Filter for NormalizeRedactions<'_> {
     fn filter(&self, data: Data) -> Data {
@@ -138,26 +432,55 @@ impl Filter for NormalizeRedactions<'_> {
             DataValue::Error(err) => DataValue::Error(err),
             DataValue::Binary(bin) => DataValue::Binary(bin),
             DataValue::Text(text) => {
-                let lines = self.redactions.redact(&text);
+                let lines = self.redact(&text);
                 DataValue::Text(lines)
             }
             #[cfg(feature = "json")]
             DataValue::Json(value) => {
                 let mut value = value;
-                normalize_json_string(&mut value, &|s| self.redactions.redact(s));
+                normalize_json_string(&mut value, &|s| self.redact(s));
+                self.redactions.redact_paths(&mut value);
                 DataValue::Json(value)
             }
             #[cfg(feature = "json")]
             DataValue::JsonLines(value) => {
                 let mut value = value;
-                normalize_json_string(&mut value, &|s| self.redactions.redact(s));
+                normalize_json_string(&mut value, &|s| self.redact(s));
+                self.redactions.redact_paths(&mut value);
                 DataValue::JsonLines(value)
             }
+            #[cfg(feature = "yaml")]
+            DataValue::Yaml(value) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &|s| self.redact(s));
+                self.redactions.redact_paths(&mut value);
+                DataValue::Yaml(value)
+            }
+            #[cfg(feature = "toml")]
+            DataValue::Toml(value, datetimes) => {
+                let mut value = value;
+                normalize_json_string(&mut value, &|s| self.redact(s));
+                self.redactions.redact_paths(&mut value);
+                DataValue::Toml(value, datetimes)
+            }
+            #[cfg(feature = "csv")]
+            DataValue::Csv(records) => {
+                let mut records = records;
+                normalize_records(&mut records, &|s| self.redact(s));
+                DataValue::Csv(records)
+            }
+            #[cfg(feature = "csv")]
+            DataValue::Tsv(records) => {
+                let mut records = records;
+                normalize_records(&mut records, &|s| self.redact(s));
+                DataValue::Tsv(records)
+            }
             #[cfg(feature = "term-svg")]
             DataValue::TermSvg(text) => {
-                let lines = self.redactions.redact(&text);
+                let lines = self.redact(&text);
                 DataValue::TermSvg(lines)
             }
+            DataValue::Custom(value) => DataValue::Custom(value),
         };
         Data {
             inner: Box::new(crate::data::DataInner {
@@ -190,3 +513,12 @@ fn normalize_json_string(value: &mut serde_json::Value, op: &dyn Fn(&str) -> Str
         _ => {}
     }
 }
+
+#[cfg(feature = "csv")]
+fn normalize_records(records: &mut [Vec<String>], op: &dyn Fn(&str) -> String) {
+    for record in records.iter_mut() {
+        for cell in record.iter_mut() {
+            *cell = op(cell);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::redactions::Backrefs;
 use super::{Filter, NormalizeRedactions, Redactions};
 use crate::Data;
 use crate::data::DataValue;
@@ -6,6 +7,7 @@ use crate::data::DataValue;
 pub struct NormalizeToExpected<'a> {
     substitutions: Option<&'a Redactions>,
     unordered: bool,
+    float_tolerance: Option<f64>,
 }
 
 impl<'a> NormalizeToExpected<'a> {
@@ -13,6 +15,7 @@ impl<'a> NormalizeToExpected<'a> {
         Self {
             substitutions: None,
             unordered: false,
+            float_tolerance: None,
         }
     }
 
@@ -29,6 +32,9 @@ impl<'a> NormalizeToExpected<'a> {
     /// Built-in redactions:
     /// - `...` on a line of its own: match multiple complete lines
     /// - `[..]`: match multiple characters within a line
+    /// - `[..:int]`, `[..:float]`, `[..:word]`, `[..:path]`, `[..:hex]`: match characters of that
+    ///   kind within a line
+    /// - `[~1.5]` or `[~1.5±0.01]`: match a number within a tolerance
     ///
     /// Built-ins cannot automatically be applied to `actual` but are inferred from `expected`
     pub fn redact(mut self) -> Self {
@@ -42,6 +48,9 @@ impl<'a> NormalizeToExpected<'a> {
     /// Built-in redactions:
     /// - `...` on a line of its own: match multiple complete lines
     /// - `[..]`: match multiple characters within a line
+    /// - `[..:int]`, `[..:float]`, `[..:word]`, `[..:path]`, `[..:hex]`: match characters of that
+    ///   kind within a line
+    /// - `[~1.5]` or `[~1.5±0.01]`: match a number within a tolerance
     ///
     /// Built-ins cannot automatically be applied to `actual` but are inferred from `expected`
     pub fn redact_with(mut self, redactions: &'a Redactions) -> Self {
@@ -49,23 +58,39 @@ impl<'a> NormalizeToExpected<'a> {
         self
     }
 
+    /// Treat numbers as equal when they are within `tolerance` of each other
+    ///
+    /// This applies to json numbers and to approximate placeholders that do not specify their own
+    /// tolerance.  Like other patterns, this only applies when redactions are enabled.
+    pub fn float_tolerance(mut self, tolerance: f64) -> Self {
+        self.float_tolerance = Some(tolerance);
+        self
+    }
+
     pub fn normalize(&self, actual: Data, expected: &Data) -> Data {
         let actual = if let Some(substitutions) = self.substitutions {
+            let backrefs = expected
+                .render()
+                .map(|pattern| Backrefs::from_pattern(&pattern))
+                .unwrap_or_default();
             NormalizeRedactions {
                 redactions: substitutions,
+                backrefs: backrefs.into(),
             }
             .filter(actual)
         } else {
             actual
         };
-        match (self.substitutions, self.unordered) {
+        let matcher = self.substitutions.map(|redactions| Matcher {
+            redactions,
+            float_tolerance: self.float_tolerance,
+        });
+        match (matcher, self.unordered) {
             (None, false) => actual,
-            (Some(substitutions), false) => {
-                normalize_data_to_redactions(actual, expected, substitutions)
-            }
+            (Some(matcher), false) => normalize_data_to_redactions(actual, expected, matcher),
             (None, true) => normalize_data_to_unordered(actual, expected),
-            (Some(substitutions), true) => {
-                normalize_data_to_unordered_redactions(actual, expected, substitutions)
+            (Some(matcher), true) => {
+                normalize_data_to_unordered_redactions(actual, expected, matcher)
             }
         }
     }
@@ -77,6 +102,13 @@ impl Default for NormalizeToExpected<'_> {
     }
 }
 
+/// Settings for matching `actual` against the patterns in `expected`
+#[derive(Copy, Clone)]
+struct Matcher<'a> {
+    redactions: &'a Redactions,
+    float_tolerance: Option<f64>,
+}
+
 fn normalize_data_to_unordered(actual: Data, expected: &Data) -> Data {
     let source = actual.inner.source;
     let filters = actual.inner.filters;
@@ -103,6 +135,26 @@ fn normalize_data_to_unordered(actual: Data, expected: &Data) -> Data {
             normalize_value_to_unordered(&mut value, exp);
             DataValue::JsonLines(value)
         }
+        #[cfg(feature = "yaml")]
+        (DataValue::Yaml(value), DataValue::Yaml(exp)) => {
+            let mut value = value;
+            normalize_value_to_unordered(&mut value, exp);
+            DataValue::Yaml(value)
+        }
+        #[cfg(feature = "toml")]
+        (DataValue::Toml(value, datetimes), DataValue::Toml(exp, _)) => {
+            let mut value = value;
+            normalize_value_to_unordered(&mut value, exp);
+            DataValue::Toml(value, datetimes)
+        }
+        #[cfg(feature = "csv")]
+        (DataValue::Csv(records), DataValue::Csv(exp)) => {
+            DataValue::Csv(normalize_records_to_unordered(records, exp))
+        }
+        #[cfg(feature = "csv")]
+        (DataValue::Tsv(records), DataValue::Tsv(exp)) => {
+            DataValue::Tsv(normalize_records_to_unordered(records, exp))
+        }
         #[cfg(feature = "term-svg")]
         (DataValue::TermSvg(text), DataValue::TermSvg(exp)) => {
             if let (Some((header, body, footer)), Some((_, exp, _))) = (
@@ -115,6 +167,9 @@ fn normalize_data_to_unordered(actual: Data, expected: &Data) -> Data {
                 DataValue::TermSvg(text)
             }
         }
+        (DataValue::Custom(value), DataValue::Custom(exp)) => {
+            DataValue::Custom(value.normalize_to_expected(exp, &Redactions::new()))
+        }
         // reachable if more than one structured data format is enabled
         #[allow(unreachable_patterns)]
         (inner, _) => inner,
@@ -170,6 +225,30 @@ fn normalize_value_to_unordered(actual: &mut serde_json::Value, expected: &serde
     }
 }
 
+#[cfg(feature = "csv")]
+fn normalize_records_to_unordered(
+    actual: Vec<Vec<String>>,
+    expected: &[Vec<String>],
+) -> Vec<Vec<String>> {
+    if actual == expected {
+        return actual;
+    }
+
+    let mut normalized: Vec<Vec<String>> = Vec::new();
+    let mut actual_records = actual;
+    for expected_record in expected {
+        if let Some(index) = actual_records
+            .iter()
+            .position(|actual_record| actual_record == expected_record)
+        {
+            normalized.push(actual_records.remove(index));
+        }
+    }
+    normalized.extend(actual_records);
+
+    normalized
+}
+
 fn normalize_str_to_unordered(actual: &str, expected: &str) -> String {
     if actual == expected {
         return actual.to_owned();
@@ -208,7 +287,7 @@ const VALUE_WILDCARD: &str = "{...}";
 fn normalize_data_to_unordered_redactions(
     actual: Data,
     expected: &Data,
-    substitutions: &Redactions,
+    matcher: Matcher<'_>,
 ) -> Data {
     let source = actual.inner.source;
     let filters = actual.inner.filters;
@@ -217,7 +296,7 @@ fn normalize_data_to_unordered_redactions(
         (DataValue::Binary(bin), _) => DataValue::Binary(bin),
         (DataValue::Text(text), _) => {
             if let Some(pattern) = expected.render() {
-                let lines = normalize_str_to_unordered_redactions(&text, &pattern, substitutions);
+                let lines = normalize_str_to_unordered_redactions(&text, &pattern, matcher);
                 DataValue::Text(lines)
             } else {
                 DataValue::Text(text)
@@ -226,27 +305,50 @@ fn normalize_data_to_unordered_redactions(
         #[cfg(feature = "json")]
         (DataValue::Json(value), DataValue::Json(exp)) => {
             let mut value = value;
-            normalize_value_to_unordered_redactions(&mut value, exp, substitutions);
+            normalize_value_to_unordered_redactions(&mut value, exp, matcher);
             DataValue::Json(value)
         }
         #[cfg(feature = "json")]
         (DataValue::JsonLines(value), DataValue::JsonLines(exp)) => {
             let mut value = value;
-            normalize_value_to_unordered_redactions(&mut value, exp, substitutions);
+            normalize_value_to_unordered_redactions(&mut value, exp, matcher);
             DataValue::JsonLines(value)
         }
+        #[cfg(feature = "yaml")]
+        (DataValue::Yaml(value), DataValue::Yaml(exp)) => {
+            let mut value = value;
+            normalize_value_to_unordered_redactions(&mut value, exp, matcher);
+            DataValue::Yaml(value)
+        }
+        #[cfg(feature = "toml")]
+        (DataValue::Toml(value, datetimes), DataValue::Toml(exp, _)) => {
+            let mut value = value;
+            normalize_value_to_unordered_redactions(&mut value, exp, matcher);
+            DataValue::Toml(value, datetimes)
+        }
+        #[cfg(feature = "csv")]
+        (DataValue::Csv(records), DataValue::Csv(exp)) => DataValue::Csv(
+            normalize_records_to_unordered_redactions(records, exp, matcher),
+        ),
+        #[cfg(feature = "csv")]
+        (DataValue::Tsv(records), DataValue::Tsv(exp)) => DataValue::Tsv(
+            normalize_records_to_unordered_redactions(records, exp, matcher),
+        ),
         #[cfg(feature = "term-svg")]
         (DataValue::TermSvg(text), DataValue::TermSvg(exp)) => {
             if let (Some((header, body, footer)), Some((_, exp, _))) = (
                 crate::data::split_term_svg(&text),
                 crate::data::split_term_svg(exp),
             ) {
-                let lines = normalize_str_to_unordered_redactions(body, exp, substitutions);
+                let lines = normalize_str_to_unordered_redactions(body, exp, matcher);
                 DataValue::TermSvg(format!("{header}{lines}{footer}"))
             } else {
                 DataValue::TermSvg(text)
             }
         }
+        (DataValue::Custom(value), DataValue::Custom(exp)) => {
+            DataValue::Custom(value.normalize_to_expected(exp, matcher.redactions))
+        }
         // reachable if more than one structured data format is enabled
         #[allow(unreachable_patterns)]
         (inner, _) => inner,
@@ -264,7 +366,7 @@ fn normalize_data_to_unordered_redactions(
 fn normalize_value_to_unordered_redactions(
     actual: &mut serde_json::Value,
     expected: &serde_json::Value,
-    substitutions: &Redactions,
+    matcher: Matcher<'_>,
 ) {
     use serde_json::Value::{Array, Object, String};
 
@@ -272,11 +374,14 @@ fn normalize_value_to_unordered_redactions(
         (act, String(exp)) if exp == VALUE_WILDCARD => {
             *act = serde_json::json!(VALUE_WILDCARD);
         }
+        (act, exp) if value_approx_matches(act, exp, matcher) => {
+            *act = exp.clone();
+        }
         (String(act), String(exp)) => {
-            *act = normalize_str_to_unordered_redactions(act, exp, substitutions);
+            *act = normalize_str_to_unordered_redactions(act, exp, matcher);
         }
         (Array(act), Array(exp)) => {
-            *act = normalize_array_to_unordered_redactions(act, exp, substitutions);
+            *act = normalize_array_to_unordered_redactions(act, exp, matcher);
         }
         (Object(act), Object(exp)) => {
             let has_key_wildcard =
@@ -286,7 +391,7 @@ fn normalize_value_to_unordered_redactions(
                     normalize_value_to_unordered_redactions(
                         &mut actual_value,
                         expected_value,
-                        substitutions,
+                        matcher,
                     );
                 } else if has_key_wildcard {
                     continue;
@@ -305,7 +410,7 @@ fn normalize_value_to_unordered_redactions(
 fn normalize_array_to_unordered_redactions(
     actual: &[serde_json::Value],
     expected: &[serde_json::Value],
-    substitutions: &Redactions,
+    matcher: Matcher<'_>,
 ) -> Vec<serde_json::Value> {
     if actual == expected {
         return actual.to_owned();
@@ -326,7 +431,7 @@ fn normalize_array_to_unordered_redactions(
                 normalize_value_to_unordered_redactions(
                     &mut normalized_actual_value,
                     expected_value,
-                    substitutions,
+                    matcher,
                 );
                 if !matched && normalized_actual_value == *expected_value {
                     matched = true;
@@ -353,7 +458,7 @@ fn normalize_array_to_unordered_redactions(
 fn normalize_str_to_unordered_redactions(
     actual: &str,
     expected: &str,
-    substitutions: &Redactions,
+    matcher: Matcher<'_>,
 ) -> String {
     if actual == expected {
         return actual.to_owned();
@@ -370,7 +475,7 @@ fn normalize_str_to_unordered_redactions(
             elided = true;
         } else {
             actual_lines.retain(|actual_line| {
-                if !matched && line_matches(actual_line, expected_line, substitutions) {
+                if !matched && line_matches(actual_line, expected_line, matcher) {
                     matched = true;
                     false
                 } else {
@@ -392,7 +497,39 @@ fn normalize_str_to_unordered_redactions(
     normalized.join("")
 }
 
-fn normalize_data_to_redactions(actual: Data, expected: &Data, substitutions: &Redactions) -> Data {
+#[cfg(feature = "csv")]
+fn normalize_records_to_unordered_redactions(
+    actual: Vec<Vec<String>>,
+    expected: &[Vec<String>],
+    matcher: Matcher<'_>,
+) -> Vec<Vec<String>> {
+    if actual == expected {
+        return actual;
+    }
+
+    let mut normalized: Vec<Vec<String>> = Vec::new();
+    let mut actual_records = actual;
+    let mut elided = false;
+    for expected_record in expected {
+        if is_record_elide(expected_record) {
+            elided = true;
+            normalized.push(expected_record.clone());
+        } else if let Some(index) = actual_records
+            .iter()
+            .position(|actual_record| record_matches(actual_record, expected_record, matcher))
+        {
+            actual_records.remove(index);
+            normalized.push(expected_record.clone());
+        }
+    }
+    if !elided {
+        normalized.extend(actual_records);
+    }
+
+    normalized
+}
+
+fn normalize_data_to_redactions(actual: Data, expected: &Data, matcher: Matcher<'_>) -> Data {
     let source = actual.inner.source;
     let filters = actual.inner.filters;
     let inner = match (actual.inner.value, &expected.inner.value) {
@@ -400,7 +537,7 @@ fn normalize_data_to_redactions(actual: Data, expected: &Data, substitutions: &R
         (DataValue::Binary(bin), _) => DataValue::Binary(bin),
         (DataValue::Text(text), _) => {
             if let Some(pattern) = expected.render() {
-                let lines = normalize_str_to_redactions(&text, &pattern, substitutions);
+                let lines = normalize_str_to_redactions(&text, &pattern, matcher);
                 DataValue::Text(lines)
             } else {
                 DataValue::Text(text)
@@ -409,27 +546,50 @@ fn normalize_data_to_redactions(actual: Data, expected: &Data, substitutions: &R
         #[cfg(feature = "json")]
         (DataValue::Json(value), DataValue::Json(exp)) => {
             let mut value = value;
-            normalize_value_to_redactions(&mut value, exp, substitutions);
+            normalize_value_to_redactions(&mut value, exp, matcher);
             DataValue::Json(value)
         }
         #[cfg(feature = "json")]
         (DataValue::JsonLines(value), DataValue::JsonLines(exp)) => {
             let mut value = value;
-            normalize_value_to_redactions(&mut value, exp, substitutions);
+            normalize_value_to_redactions(&mut value, exp, matcher);
             DataValue::JsonLines(value)
         }
+        #[cfg(feature = "yaml")]
+        (DataValue::Yaml(value), DataValue::Yaml(exp)) => {
+            let mut value = value;
+            normalize_value_to_redactions(&mut value, exp, matcher);
+            DataValue::Yaml(value)
+        }
+        #[cfg(feature = "toml")]
+        (DataValue::Toml(value, datetimes), DataValue::Toml(exp, _)) => {
+            let mut value = value;
+            normalize_value_to_redactions(&mut value, exp, matcher);
+            DataValue::Toml(value, datetimes)
+        }
+        #[cfg(feature = "csv")]
+        (DataValue::Csv(records), DataValue::Csv(exp)) => {
+            DataValue::Csv(normalize_records_to_redactions(records, exp, matcher))
+        }
+        #[cfg(feature = "csv")]
+        (DataValue::Tsv(records), DataValue::Tsv(exp)) => {
+            DataValue::Tsv(normalize_records_to_redactions(records, exp, matcher))
+        }
         #[cfg(feature = "term-svg")]
         (DataValue::TermSvg(text), DataValue::TermSvg(exp)) => {
             if let (Some((header, body, footer)), Some((_, exp, _))) = (
                 crate::data::split_term_svg(&text),
                 crate::data::split_term_svg(exp),
             ) {
-                let lines = normalize_str_to_redactions(body, exp, substitutions);
+                let lines = normalize_str_to_redactions(body, exp, matcher);
                 DataValue::TermSvg(format!("{header}{lines}{footer}"))
             } else {
                 DataValue::TermSvg(text)
             }
         }
+        (DataValue::Custom(value), DataValue::Custom(exp)) => {
+            DataValue::Custom(value.normalize_to_expected(exp, matcher.redactions))
+        }
         // reachable if more than one structured data format is enabled
         #[allow(unreachable_patterns)]
         (inner, _) => inner,
@@ -447,7 +607,7 @@ fn normalize_data_to_redactions(actual: Data, expected: &Data, substitutions: &R
 fn normalize_value_to_redactions(
     actual: &mut serde_json::Value,
     expected: &serde_json::Value,
-    substitutions: &Redactions,
+    matcher: Matcher<'_>,
 ) {
     use serde_json::Value::{Array, Object, String};
 
@@ -455,18 +615,21 @@ fn normalize_value_to_redactions(
         (act, String(exp)) if exp == VALUE_WILDCARD => {
             *act = serde_json::json!(VALUE_WILDCARD);
         }
+        (act, exp) if value_approx_matches(act, exp, matcher) => {
+            *act = exp.clone();
+        }
         (String(act), String(exp)) => {
-            *act = normalize_str_to_redactions(act, exp, substitutions);
+            *act = normalize_str_to_redactions(act, exp, matcher);
         }
         (Array(act), Array(exp)) => {
-            *act = normalize_array_to_redactions(act, exp, substitutions);
+            *act = normalize_array_to_redactions(act, exp, matcher);
         }
         (Object(act), Object(exp)) => {
             let has_key_wildcard =
                 exp.get(KEY_WILDCARD).and_then(|v| v.as_str()) == Some(VALUE_WILDCARD);
             for (actual_key, mut actual_value) in std::mem::replace(act, serde_json::Map::new()) {
                 if let Some(expected_value) = exp.get(&actual_key) {
-                    normalize_value_to_redactions(&mut actual_value, expected_value, substitutions);
+                    normalize_value_to_redactions(&mut actual_value, expected_value, matcher);
                 } else if has_key_wildcard {
                     continue;
                 }
@@ -484,7 +647,7 @@ fn normalize_value_to_redactions(
 fn normalize_array_to_redactions(
     actual: &[serde_json::Value],
     expected: &[serde_json::Value],
-    redactions: &Redactions,
+    matcher: Matcher<'_>,
 ) -> Vec<serde_json::Value> {
     if actual == expected {
         return actual.to_vec();
@@ -503,11 +666,7 @@ fn normalize_array_to_redactions(
             };
             let Some(index_offset) = actual[actual_index..].iter().position(|next_actual_elem| {
                 let mut next_actual_elem = next_actual_elem.clone();
-                normalize_value_to_redactions(
-                    &mut next_actual_elem,
-                    next_expected_elem,
-                    redactions,
-                );
+                normalize_value_to_redactions(&mut next_actual_elem, next_expected_elem, matcher);
                 next_actual_elem == **next_expected_elem
             }) else {
                 // Give up as we can't find where the elide ends
@@ -523,7 +682,7 @@ fn normalize_array_to_redactions(
 
             actual_index += 1;
             let mut normalized_elem = actual_elem.clone();
-            normalize_value_to_redactions(&mut normalized_elem, expected_elem, redactions);
+            normalize_value_to_redactions(&mut normalized_elem, expected_elem, matcher);
             normalized.push(normalized_elem);
         }
     }
@@ -532,7 +691,7 @@ fn normalize_array_to_redactions(
     normalized
 }
 
-fn normalize_str_to_redactions(actual: &str, expected: &str, redactions: &Redactions) -> String {
+fn normalize_str_to_redactions(actual: &str, expected: &str, matcher: Matcher<'_>) -> String {
     if actual == expected {
         return actual.to_owned();
     }
@@ -553,7 +712,7 @@ fn normalize_str_to_redactions(actual: &str, expected: &str, redactions: &Redact
                 actual_lines[actual_index..]
                     .iter()
                     .position(|next_actual_line| {
-                        line_matches(next_actual_line, next_expected_line, redactions)
+                        line_matches(next_actual_line, next_expected_line, matcher)
                     })
             else {
                 // Give up as we can't find where the elide ends
@@ -567,7 +726,7 @@ fn normalize_str_to_redactions(actual: &str, expected: &str, redactions: &Redact
                 break;
             };
 
-            if line_matches(actual_line, expected_line, redactions) {
+            if line_matches(actual_line, expected_line, matcher) {
                 actual_index += 1;
                 normalized.push(expected_line);
             } else {
@@ -582,23 +741,105 @@ fn normalize_str_to_redactions(actual: &str, expected: &str, redactions: &Redact
     normalized.join("")
 }
 
+#[cfg(feature = "csv")]
+fn normalize_records_to_redactions(
+    actual: Vec<Vec<String>>,
+    expected: &[Vec<String>],
+    matcher: Matcher<'_>,
+) -> Vec<Vec<String>> {
+    if actual == expected {
+        return actual;
+    }
+
+    let mut normalized: Vec<Vec<String>> = Vec::new();
+    let mut actual_index = 0;
+    let mut expected_records = expected.iter().peekable();
+    while let Some(expected_record) = expected_records.next() {
+        if is_record_elide(expected_record) {
+            let Some(next_expected_record) = expected_records.peek() else {
+                // Stop as elide consumes to end
+                normalized.push(expected_record.clone());
+                actual_index = actual.len();
+                break;
+            };
+            let Some(index_offset) = actual[actual_index..]
+                .iter()
+                .position(|next_actual_record| {
+                    record_matches(next_actual_record, next_expected_record, matcher)
+                })
+            else {
+                // Give up as we can't find where the elide ends
+                break;
+            };
+            normalized.push(expected_record.clone());
+            actual_index += index_offset;
+        } else {
+            let Some(actual_record) = actual.get(actual_index) else {
+                // Give up as we have no more content to check
+                break;
+            };
+
+            actual_index += 1;
+            normalized.push(normalize_record_to_redactions(
+                actual_record,
+                expected_record,
+                matcher,
+            ));
+        }
+    }
+
+    normalized.extend(actual[actual_index..].iter().cloned());
+    normalized
+}
+
+/// Normalize each cell independently so mismatches are reported per cell
+#[cfg(feature = "csv")]
+fn normalize_record_to_redactions(
+    actual: &[String],
+    expected: &[String],
+    matcher: Matcher<'_>,
+) -> Vec<String> {
+    actual
+        .iter()
+        .enumerate()
+        .map(|(i, actual_cell)| match expected.get(i) {
+            Some(expected_cell) => normalize_str_to_redactions(actual_cell, expected_cell, matcher),
+            None => actual_cell.clone(),
+        })
+        .collect()
+}
+
+#[cfg(feature = "csv")]
+fn record_matches(actual: &[String], expected: &[String], matcher: Matcher<'_>) -> bool {
+    actual.len() == expected.len()
+        && normalize_record_to_redactions(actual, expected, matcher) == expected
+}
+
+#[cfg(feature = "csv")]
+fn is_record_elide(record: &[String]) -> bool {
+    matches!(record, [cell] if is_line_elide(cell))
+}
+
 fn is_line_elide(line: &str) -> bool {
     line == "...\n" || line == "..."
 }
 
-fn line_matches(mut actual: &str, expected: &str, redactions: &Redactions) -> bool {
+fn line_matches(mut actual: &str, expected: &str, matcher: Matcher<'_>) -> bool {
     if actual == expected {
         return true;
     }
 
-    let expected = redactions.clear_unused(expected);
-    let mut sections = expected.split("[..]").peekable();
+    let expected = matcher.redactions.clear_unused(expected);
+    let segments = parse_segments(&expected);
+    let mut sections = segments
+        .split(|segment| matches!(segment, Segment::Wildcard))
+        .peekable();
     while let Some(section) = sections.next() {
-        if let Some(remainder) = actual.strip_prefix(section) {
+        if let Some(remainder) = strip_section(actual, section, matcher) {
             if let Some(next_section) = sections.peek() {
                 if next_section.is_empty() {
                     actual = "";
-                } else if let Some(restart_index) = remainder.find(next_section) {
+                } else if let Some(restart_index) = find_section(remainder, next_section, matcher) {
                     actual = &remainder[restart_index..];
                 }
             } else {
@@ -612,6 +853,261 @@ fn line_matches(mut actual: &str, expected: &str, redactions: &Redactions) -> bo
     false
 }
 
+enum Segment<'p> {
+    Literal(&'p str),
+    /// `[..]`
+    Wildcard,
+    /// `[..:int]`, etc
+    TypedWildcard(WildcardKind),
+    /// `[~1.5]` or `[~1.5±0.01]`
+    Approx {
+        value: f64,
+        tolerance: Option<f64>,
+    },
+    /// `[NAME]`, which also matches it being numbered as a back-reference, like `[NAME#2]`
+    Redacted(&'p str),
+}
+
+fn parse_segments(mut pattern: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = pattern[offset..].find('[').map(|index| offset + index) {
+        let Some((segment, len)) = parse_placeholder(&pattern[start..]) else {
+            offset = start + 1;
+            continue;
+        };
+        if 0 < start {
+            segments.push(Segment::Literal(&pattern[..start]));
+        }
+        segments.push(segment);
+        pattern = &pattern[start + len..];
+        offset = 0;
+    }
+    if !pattern.is_empty() {
+        segments.push(Segment::Literal(pattern));
+    }
+    segments
+}
+
+/// Returns the segment and how many bytes of `pattern` it spans
+fn parse_placeholder(pattern: &str) -> Option<(Segment<'_>, usize)> {
+    if pattern.starts_with("[..]") {
+        return Some((Segment::Wildcard, "[..]".len()));
+    }
+    let end = pattern.find(']')?;
+    if let Some(kind) = pattern[1..end].strip_prefix("..:") {
+        let kind = WildcardKind::parse(kind)?;
+        return Some((Segment::TypedWildcard(kind), end + 1));
+    }
+    if super::redactions::is_placeholder_name(&pattern[1..end]) {
+        return Some((Segment::Redacted(&pattern[..=end]), end + 1));
+    }
+    let (value, tolerance) = parse_approx(pattern[1..end].strip_prefix('~')?)?;
+    Some((Segment::Approx { value, tolerance }, end + 1))
+}
+
+/// Where `section` first matches in `actual`, like [`str::find`]
+fn find_section(actual: &str, section: &[Segment<'_>], matcher: Matcher<'_>) -> Option<usize> {
+    if let Some(Segment::Literal(literal)) = section.first() {
+        actual
+            .match_indices(literal)
+            .map(|(index, _)| index)
+            .find(|index| strip_section(&actual[*index..], section, matcher).is_some())
+    } else {
+        actual
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain([actual.len()])
+            .find(|index| strip_section(&actual[*index..], section, matcher).is_some())
+    }
+}
+
+/// Match the placeholders between `[..]`s at the start of `actual`, like [`str::strip_prefix`]
+///
+/// Each placeholder matches as much as it can, only giving some back for the literal text right
+/// after it.
+fn strip_section<'a>(
+    mut actual: &'a str,
+    section: &[Segment<'_>],
+    matcher: Matcher<'_>,
+) -> Option<&'a str> {
+    for (i, segment) in section.iter().enumerate() {
+        actual = match segment {
+            Segment::Literal(literal) => actual.strip_prefix(literal)?,
+            Segment::Wildcard => unreachable!("sections are split on wildcards"),
+            Segment::TypedWildcard(kind) => {
+                let next_literal = match section.get(i + 1) {
+                    Some(Segment::Literal(literal)) => literal,
+                    _ => "",
+                };
+                let end = actual[..kind.run_len(actual)]
+                    .char_indices()
+                    .map(|(index, c)| index + c.len_utf8())
+                    .rev()
+                    .find(|end| {
+                        kind.accepts(&actual[..*end]) && actual[*end..].starts_with(next_literal)
+                    })?;
+                &actual[end..]
+            }
+            Segment::Approx { value, tolerance } => {
+                let len = number_len(actual);
+                let number = actual[..len].parse::<f64>().ok()?;
+                if len == 0 || !approx_eq(number, *value, tolerance.or(matcher.float_tolerance)) {
+                    return None;
+                }
+                &actual[len..]
+            }
+            Segment::Redacted(placeholder) => {
+                if let Some(rest) = actual.strip_prefix(placeholder) {
+                    rest
+                } else {
+                    let name = &placeholder[..placeholder.len() - 1];
+                    let number = actual.strip_prefix(name)?.strip_prefix('#')?;
+                    let len = number.bytes().take_while(u8::is_ascii_digit).count();
+                    if len == 0 {
+                        return None;
+                    }
+                    number[len..].strip_prefix(']')?
+                }
+            }
+        };
+    }
+    Some(actual)
+}
+
+#[derive(Copy, Clone)]
+enum WildcardKind {
+    Int,
+    Float,
+    Word,
+    Path,
+    Hex,
+}
+
+impl WildcardKind {
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "int" => Some(Self::Int),
+            "float" => Some(Self::Float),
+            "word" => Some(Self::Word),
+            "path" => Some(Self::Path),
+            "hex" => Some(Self::Hex),
+            _ => None,
+        }
+    }
+
+    /// Length of the longest prefix of `text` that could contain a match
+    fn run_len(self, text: &str) -> usize {
+        let allowed = |c: char| match self {
+            Self::Int => c.is_ascii_digit() || c == '-',
+            Self::Float => c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'),
+            Self::Word => c.is_alphanumeric() || c == '_',
+            Self::Path => {
+                c.is_alphanumeric()
+                    || matches!(c, '/' | '\\' | '.' | '-' | '_' | '~' | ':' | '+' | '@')
+            }
+            Self::Hex => c.is_ascii_hexdigit() || matches!(c, 'x' | 'X'),
+        };
+        text.find(|c: char| !allowed(c)).unwrap_or(text.len())
+    }
+
+    fn accepts(self, token: &str) -> bool {
+        match self {
+            Self::Int => {
+                let digits = token.strip_prefix('-').unwrap_or(token);
+                !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+            }
+            Self::Float => !token.starts_with('+') && number_len(token) == token.len(),
+            Self::Word | Self::Path => !token.is_empty(),
+            Self::Hex => {
+                let digits = token
+                    .strip_prefix("0x")
+                    .or_else(|| token.strip_prefix("0X"))
+                    .unwrap_or(token);
+                !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_hexdigit())
+            }
+        }
+    }
+}
+
+/// Length of the decimal number at the start of `text`, if any
+fn number_len(text: &str) -> usize {
+    fn digits_len(text: &[u8]) -> usize {
+        text.iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+
+    let bytes = text.as_bytes();
+    let mut len = usize::from(matches!(bytes.first(), Some(b'-' | b'+')));
+    let integer_len = digits_len(&bytes[len..]);
+    len += integer_len;
+    let mut fraction_len = 0;
+    if bytes.get(len) == Some(&b'.') {
+        fraction_len = digits_len(&bytes[len + 1..]);
+        if 0 < fraction_len {
+            len += 1 + fraction_len;
+        }
+    }
+    if integer_len == 0 && fraction_len == 0 {
+        return 0;
+    }
+    if let Some(b'e' | b'E') = bytes.get(len) {
+        let sign_len = usize::from(matches!(bytes.get(len + 1), Some(b'-' | b'+')));
+        let exponent_len = digits_len(&bytes[len + 1 + sign_len..]);
+        if 0 < exponent_len {
+            len += 1 + sign_len + exponent_len;
+        }
+    }
+    len
+}
+
+/// Parse `1.5` or `1.5±0.01` from an approximate placeholder
+fn parse_approx(pattern: &str) -> Option<(f64, Option<f64>)> {
+    match pattern.split_once('±') {
+        Some((value, tolerance)) => {
+            let value = value.trim().parse().ok()?;
+            let tolerance = tolerance.trim().parse().ok()?;
+            Some((value, Some(tolerance)))
+        }
+        None => Some((pattern.trim().parse().ok()?, None)),
+    }
+}
+
+/// Without a `tolerance`, allow for rounding differences in the last few bits
+fn approx_eq(actual: f64, expected: f64, tolerance: Option<f64>) -> bool {
+    let tolerance =
+        tolerance.unwrap_or_else(|| 4.0 * f64::EPSILON * actual.abs().max(expected.abs()));
+    (actual - expected).abs() <= tolerance
+}
+
+#[cfg(feature = "structured-data")]
+fn value_approx_matches(
+    actual: &serde_json::Value,
+    expected: &serde_json::Value,
+    matcher: Matcher<'_>,
+) -> bool {
+    let Some(actual) = actual.as_f64() else {
+        return false;
+    };
+    match expected {
+        serde_json::Value::Number(expected) => {
+            let Some(tolerance) = matcher.float_tolerance else {
+                return false;
+            };
+            expected
+                .as_f64()
+                .is_some_and(|expected| approx_eq(actual, expected, Some(tolerance)))
+        }
+        serde_json::Value::String(expected) => expected
+            .strip_prefix("{~")
+            .and_then(|pattern| pattern.strip_suffix('}'))
+            .and_then(parse_approx)
+            .is_some_and(|(expected, tolerance)| {
+                approx_eq(actual, expected, tolerance.or(matcher.float_tolerance))
+            }),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -654,10 +1150,111 @@ mod test {
                 false,
             ),
             ("hello world, goodbye moon", "hello [..], [..] world", false),
+            ("a-b-c", "[..]-c", true),
+            ("a-b-c", "a[..]b[..]", true),
+            ("hello [..]", "hello [..]", true),
+            ("hello [", "hello [", true),
+            // `[..]` skips to the first occurrence of what follows
+            ("abxb", "a[..]b", false),
+            ("a-b-b", "[..]-b", false),
+        ];
+        for (line, pattern, expected) in cases {
+            let matcher = Matcher {
+                redactions: &Redactions::new(),
+                float_tolerance: None,
+            };
+            let actual = line_matches(line, pattern, matcher);
+            assert_eq!(expected, actual, "line={line:?}  pattern={pattern:?}");
+        }
+    }
+
+    #[test]
+    fn str_normalize_redactions_line_matches_approx_cases() {
+        let cases = [
+            ("1.5", "[~1.5]", true),
+            ("1.5000000000000002", "[~1.5]", true),
+            ("1.5001", "[~1.5]", false),
+            ("1.5001", "[~1.5±0.01]", true),
+            ("1.6", "[~1.5±0.01]", false),
+            ("-2e3", "[~-2000]", true),
+            ("took 0.98s", "took [~1±0.05]s", true),
+            ("took 1.10s", "took [~1±0.05]s", false),
+            ("took s", "took [~1±0.05]s", false),
+            ("x=1.5, y=2.5", "x=[..], y=[~2.5]", true),
+            ("x=1.5, y=2.5", "[..]=[~2.5]", true),
+            ("x=1.5, y=2.5", "[..]=[~1.5]", false),
+            ("1.5", "[~one]", false),
+            ("[~one]", "[~one]", true),
+        ];
+        for (line, pattern, expected) in cases {
+            let matcher = Matcher {
+                redactions: &Redactions::new(),
+                float_tolerance: None,
+            };
+            let actual = line_matches(line, pattern, matcher);
+            assert_eq!(expected, actual, "line={line:?}  pattern={pattern:?}");
+        }
+    }
+
+    #[test]
+    fn str_normalize_redactions_line_matches_typed_cases() {
+        let cases = [
+            ("took 15 ms", "took [..:int] ms", true),
+            ("took -15 ms", "took [..:int] ms", true),
+            ("took 1.5 ms", "took [..:int] ms", false),
+            ("took error ms", "took [..:int] ms", false),
+            ("took  ms", "took [..:int] ms", false),
+            ("123456", "[..:int]56", true),
+            ("took 1.5 ms", "took [..:float] ms", true),
+            ("took -1e-3 ms", "took [..:float] ms", true),
+            ("took 15 ms", "took [..:float] ms", true),
+            ("took 1. ms", "took [..:float] ms", false),
+            ("took NaN ms", "took [..:float] ms", false),
+            ("hello world", "hello [..:word]", true),
+            ("hello big world", "hello [..:word]", false),
+            ("hello snake_case", "hello [..:word]", true),
+            ("hello foo_bar", "hello [..:word]_bar", true),
+            ("at src/lib.rs:10", "at [..:path]:10", true),
+            ("at C:\\src\\lib.rs", "at [..:path]", true),
+            ("at src/my lib.rs", "at [..:path]", false),
+            ("id deadBEEF", "id [..:hex]", true),
+            ("id 0xdeadbeef", "id [..:hex]", true),
+            ("id deadbeefz", "id [..:hex]", false),
+            ("1 + 2", "[..:int] + [..:int]", true),
+            ("1 + x", "[..:int] + [..:int]", false),
+            ("a-b-12", "[..]-[..:int]", true),
+            ("[..:bool]", "[..:bool]", true),
+            ("true", "[..:bool]", false),
         ];
         for (line, pattern, expected) in cases {
-            let actual = line_matches(line, pattern, &Redactions::new());
+            let matcher = Matcher {
+                redactions: &Redactions::new(),
+                float_tolerance: None,
+            };
+            let actual = line_matches(line, pattern, matcher);
             assert_eq!(expected, actual, "line={line:?}  pattern={pattern:?}");
         }
     }
+
+    #[test]
+    fn str_normalize_redactions_line_matches_many_wildcards() {
+        let matcher = Matcher {
+            redactions: &Redactions::new(),
+            float_tolerance: None,
+        };
+        let line = "a".repeat(60);
+        let pattern = format!("{}b", "[..]a".repeat(30));
+        assert!(!line_matches(&line, &pattern, matcher));
+    }
+
+    #[test]
+    fn str_normalize_redactions_line_matches_float_tolerance() {
+        let matcher = Matcher {
+            redactions: &Redactions::new(),
+            float_tolerance: Some(0.1),
+        };
+        assert!(line_matches("1.55", "[~1.5]", matcher));
+        assert!(!line_matches("1.55", "[~1.5±0.01]", matcher));
+        assert!(!line_matches("1.55", "1.5", matcher));
+    }
 }
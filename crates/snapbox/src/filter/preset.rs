@@ -0,0 +1,383 @@
+use std::ops::Range;
+
+/// Built-in matchers for common volatile values
+///
+/// Register these with [`Redactions::insert_preset`][crate::Redactions::insert_preset] to use the
+/// default placeholder or with [`Redactions::insert`][crate::Redactions::insert] to use a custom
+/// one.
+///
+/// # Examples
+///
+/// ```rust
+/// use snapbox::filter::Preset;
+///
+/// let mut redactions = snapbox::Redactions::new();
+/// redactions.insert_preset(Preset::Uuid).unwrap();
+/// redactions.insert_preset(Preset::Duration).unwrap();
+/// assert_eq!(
+///     redactions.redact("request 67e55044-10b1-426f-9247-bb680e5fe0c8 took 1.23s"),
+///     "request [UUID] took [DURATION]"
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Preset {
+    /// RFC 3339 / ISO 8601 date and time, like `2024-01-31T12:30:00.123Z`
+    Timestamp,
+    /// Hyphenated UUID, like `67e55044-10b1-426f-9247-bb680e5fe0c8`
+    Uuid,
+    /// [humantime](https://docs.rs/humantime)-style duration, like `1.23s`, `450ms` or `1h 2m 3s`
+    Duration,
+    /// Hex digest of a common length (MD5, SHA-1, SHA-2), like `d41d8cd98f00b204e9800998ecf8427e`
+    Hash,
+    /// Hex pointer address, like `0x7ffd5fbff8a0`
+    Addr,
+    /// Process ID following `pid`, like the `1234` in `pid=1234`
+    Pid,
+}
+
+impl Preset {
+    /// Placeholder used by [`Redactions::insert_preset`][crate::Redactions::insert_preset]
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            Self::Timestamp => "[TIMESTAMP]",
+            Self::Uuid => "[UUID]",
+            Self::Duration => "[DURATION]",
+            Self::Hash => "[HASH]",
+            Self::Addr => "[ADDR]",
+            Self::Pid => "[PID]",
+        }
+    }
+
+    pub(crate) fn find_in(self, buffer: &str) -> Option<Range<usize>> {
+        let matches = match self {
+            Self::Timestamp => match_timestamp,
+            Self::Uuid => match_uuid,
+            Self::Duration => match_duration,
+            Self::Hash => match_hash,
+            Self::Addr => match_addr,
+            Self::Pid => match_pid,
+        };
+        buffer
+            .char_indices()
+            .filter(|(start, _)| !buffer[..*start].chars().next_back().is_some_and(is_word))
+            .find_map(|(start, _)| {
+                let redacted = matches(&buffer[start..])?;
+                let is_end = !buffer[start + redacted.end..]
+                    .chars()
+                    .next()
+                    .is_some_and(is_word);
+                is_end.then(|| (start + redacted.start)..(start + redacted.end))
+            })
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Each matcher checks for its value at the start of `text`, returning the range to redact
+fn match_timestamp(text: &str) -> Option<Range<usize>> {
+    let mut scanner = Scanner::new(text);
+    scanner.digits(4)?;
+    scanner.byte(b"-")?;
+    scanner.digits(2)?;
+    scanner.byte(b"-")?;
+    scanner.digits(2)?;
+    scanner.byte(b"Tt ")?;
+    scanner.digits(2)?;
+    scanner.byte(b":")?;
+    scanner.digits(2)?;
+    scanner.optional(|s| {
+        s.byte(b":")?;
+        s.digits(2)?;
+        s.optional(|s| {
+            s.byte(b".,")?;
+            s.many_digits()
+        });
+        Some(())
+    });
+    scanner.optional(|s| {
+        if s.byte(b"Zz").is_some() {
+            return Some(());
+        }
+        s.byte(b"+-")?;
+        s.digits(2)?;
+        s.optional(|s| s.byte(b":"));
+        s.digits(2)
+    });
+    Some(0..scanner.pos)
+}
+
+fn match_uuid(text: &str) -> Option<Range<usize>> {
+    let mut scanner = Scanner::new(text);
+    for (i, len) in [8, 4, 4, 4, 12].into_iter().enumerate() {
+        if i != 0 {
+            scanner.byte(b"-")?;
+        }
+        (scanner.many_hex() == len).then_some(())?;
+    }
+    Some(0..scanner.pos)
+}
+
+fn match_duration(text: &str) -> Option<Range<usize>> {
+    const UNITS: &[&str] = &["ns", "us", "µs", "ms", "s", "m", "h", "d"];
+
+    fn component(s: &mut Scanner<'_>) -> Option<()> {
+        s.many_digits()?;
+        s.optional(|s| {
+            s.byte(b".")?;
+            s.many_digits()
+        });
+        let rest = &s.text[s.pos..];
+        let unit = UNITS
+            .iter()
+            .filter(|unit| rest.starts_with(**unit))
+            .max_by_key(|unit| unit.len())?;
+        s.pos += unit.len();
+        // Allow compact durations like `1h2m`
+        let next = s.text[s.pos..].chars().next();
+        (!next.is_some_and(|c| c.is_alphabetic() || c == '_')).then_some(())
+    }
+
+    let mut scanner = Scanner::new(text);
+    component(&mut scanner)?;
+    while scanner.optional(|s| {
+        s.optional(|s| s.byte(b" "));
+        component(s)
+    }) {}
+    Some(0..scanner.pos)
+}
+
+fn match_hash(text: &str) -> Option<Range<usize>> {
+    const LENGTHS: &[usize] = &[32, 40, 56, 64, 96, 128];
+
+    let mut scanner = Scanner::new(text);
+    let len = scanner.many_hex();
+    LENGTHS.contains(&len).then_some(0..len)
+}
+
+fn match_addr(text: &str) -> Option<Range<usize>> {
+    let mut scanner = Scanner::new(text);
+    scanner.byte(b"0")?;
+    scanner.byte(b"xX")?;
+    let len = scanner.many_hex();
+    (4..=16).contains(&len).then_some(0..scanner.pos)
+}
+
+fn match_pid(text: &str) -> Option<Range<usize>> {
+    let prefix = text.get(..3)?;
+    prefix.eq_ignore_ascii_case("pid").then_some(())?;
+    let mut scanner = Scanner::new(text);
+    scanner.pos = prefix.len();
+    while scanner.byte(b" :=#").is_some() {}
+    let start = scanner.pos;
+    scanner.many_digits()?;
+    Some(start..scanner.pos)
+}
+
+struct Scanner<'t> {
+    text: &'t str,
+    pos: usize,
+}
+
+impl<'t> Scanner<'t> {
+    fn new(text: &'t str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn byte(&mut self, choices: &[u8]) -> Option<()> {
+        self.peek().filter(|b| choices.contains(b))?;
+        self.pos += 1;
+        Some(())
+    }
+
+    fn digits(&mut self, count: usize) -> Option<()> {
+        let start = self.pos;
+        while self.pos - start < count && self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        (self.pos - start == count).then_some(())
+    }
+
+    fn many_digits(&mut self) -> Option<()> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        (start < self.pos).then_some(())
+    }
+
+    fn many_hex(&mut self) -> usize {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_hexdigit()) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    /// Run `parse`, rewinding if it fails
+    fn optional(&mut self, parse: impl FnOnce(&mut Self) -> Option<()>) -> bool {
+        let start = self.pos;
+        let matched = parse(self).is_some();
+        if !matched {
+            self.pos = start;
+        }
+        matched
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn redact(preset: Preset, input: &str) -> String {
+        let mut redactions = crate::Redactions::new();
+        redactions.insert_preset(preset).unwrap();
+        redactions.redact(input)
+    }
+
+    #[track_caller]
+    fn assert_cases(preset: Preset, cases: &[(&str, &str)]) {
+        for (input, expected) in cases {
+            let actual = redact(preset, input);
+            assert_eq!(actual, *expected, "{preset:?} for {input:?}");
+        }
+    }
+
+    #[test]
+    fn timestamp() {
+        assert_cases(
+            Preset::Timestamp,
+            &[
+                ("at 2024-01-31T12:30:00Z", "at [TIMESTAMP]"),
+                ("at 2024-01-31T12:30:00.123456Z.", "at [TIMESTAMP]."),
+                ("at 2024-01-31 12:30:00+01:00", "at [TIMESTAMP]"),
+                ("at 2024-01-31t12:30:00-0800", "at [TIMESTAMP]"),
+                ("at 2024-01-31T12:30", "at [TIMESTAMP]"),
+                ("at 2024-01-31T12:30:00,5 done", "at [TIMESTAMP] done"),
+                (
+                    "2024-01-31T12:30:00Z..2024-02-01T00:00:00Z",
+                    "[TIMESTAMP]..[TIMESTAMP]",
+                ),
+                ("on 2024-01-31", "on 2024-01-31"),
+                ("v12024-01-31T12:30:00Z", "v12024-01-31T12:30:00Z"),
+                ("2024-1-31T12:30:00Z", "2024-1-31T12:30:00Z"),
+            ],
+        );
+    }
+
+    #[test]
+    fn uuid() {
+        assert_cases(
+            Preset::Uuid,
+            &[
+                ("id=67e55044-10b1-426f-9247-bb680e5fe0c8", "id=[UUID]"),
+                ("{67E55044-10B1-426F-9247-BB680E5FE0C8}", "{[UUID]}"),
+                (
+                    "67e55044-10b1-426f-9247-bb680e5fe0c8a",
+                    "67e55044-10b1-426f-9247-bb680e5fe0c8a",
+                ),
+                (
+                    "67e55044-10b1-426f-9247-bb680e5fe0c",
+                    "67e55044-10b1-426f-9247-bb680e5fe0c",
+                ),
+                (
+                    "67e5504410b1426f9247bb680e5fe0c8",
+                    "67e5504410b1426f9247bb680e5fe0c8",
+                ),
+                (
+                    "67e55044-10b1-426f-9247-bb680e5fe0g8",
+                    "67e55044-10b1-426f-9247-bb680e5fe0g8",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn duration() {
+        assert_cases(
+            Preset::Duration,
+            &[
+                ("finished in 1.23s", "finished in [DURATION]"),
+                ("finished in 450ms.", "finished in [DURATION]."),
+                (
+                    "took 12µs, 3us and 800ns",
+                    "took [DURATION], [DURATION] and [DURATION]",
+                ),
+                ("took 1h 2m 3s", "took [DURATION]"),
+                ("took 1h2m3s", "took [DURATION]"),
+                ("(2d)", "([DURATION])"),
+                ("took 5min", "took 5min"),
+                ("took 1.s", "took 1.s"),
+                ("v1s", "v1s"),
+                ("10 s", "10 s"),
+                ("3 seconds", "3 seconds"),
+            ],
+        );
+    }
+
+    #[test]
+    fn hash() {
+        assert_cases(
+            Preset::Hash,
+            &[
+                ("md5: d41d8cd98f00b204e9800998ecf8427e", "md5: [HASH]"),
+                (
+                    "sha1 da39a3ee5e6b4b0d3255bfef95601890afd80709",
+                    "sha1 [HASH]",
+                ),
+                (
+                    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855 file",
+                    "[HASH] file",
+                ),
+                ("commit abc1234", "commit abc1234"),
+                (
+                    "d41d8cd98f00b204e9800998ecf8427",
+                    "d41d8cd98f00b204e9800998ecf8427",
+                ),
+                (
+                    "d41d8cd98f00b204e9800998ecf8427ex",
+                    "d41d8cd98f00b204e9800998ecf8427ex",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn addr() {
+        assert_cases(
+            Preset::Addr,
+            &[
+                ("ptr 0x7ffd5fbff8a0", "ptr [ADDR]"),
+                ("at 0X0000DEAD:", "at [ADDR]:"),
+                ("(0x1234, 0x5678)", "([ADDR], [ADDR])"),
+                ("exit 0x1", "exit 0x1"),
+                ("0x12345678123456789", "0x12345678123456789"),
+                ("a0x1234", "a0x1234"),
+                ("0x1234g", "0x1234g"),
+            ],
+        );
+    }
+
+    #[test]
+    fn pid() {
+        assert_cases(
+            Preset::Pid,
+            &[
+                ("pid=1234", "pid=[PID]"),
+                ("PID: 42 exited", "PID: [PID] exited"),
+                ("[pid 7]", "[pid [PID]]"),
+                ("pid#99", "pid#[PID]"),
+                ("rapid 1234", "rapid 1234"),
+                ("pid=12ab", "pid=12ab"),
+                ("pid=", "pid="),
+                ("port 1234", "port 1234"),
+            ],
+        );
+    }
+}
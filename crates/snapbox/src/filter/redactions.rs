@@ -16,12 +16,26 @@ use std::path::PathBuf;
 /// subst.insert("[LOCATION]", "World");
 /// assert_eq!(subst.redact("Hello World!"), "Hello [LOCATION]!");
 /// ```
+///
+/// # Back-references
+///
+/// When `expected` data uses a numbered placeholder, like `[ID#1]`, each distinct value redacted
+/// by `[ID]` in `actual` is numbered in the order it is first seen.  This checks that a value
+/// matches everywhere it is expected to be the same, like
+/// ```text
+/// Created [ID#1]
+/// Deleted [ID#1]
+/// ```
+///
+/// A plain `[ID]` in the same data still matches any value, like `Moved [ID#1] to [ID]`.
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct Redactions {
     vars: Option<
         std::collections::BTreeMap<RedactedValueInner, std::collections::BTreeSet<&'static str>>,
     >,
     unused: Option<std::collections::BTreeSet<RedactedValueInner>>,
+    #[cfg(feature = "structured-data")]
+    paths: Option<Vec<RedactedPath>>,
 }
 
 impl Redactions {
@@ -29,6 +43,8 @@ impl Redactions {
         Self {
             vars: None,
             unused: None,
+            #[cfg(feature = "structured-data")]
+            paths: None,
         }
     }
 
@@ -83,6 +99,45 @@ impl Redactions {
         Ok(())
     }
 
+    /// Insert a built-in match pattern with its default placeholder
+    ///
+    /// ```rust
+    /// let mut subst = snapbox::Redactions::new();
+    /// subst.insert_preset(snapbox::filter::Preset::Timestamp);
+    /// assert_eq!(subst.redact("at 2024-01-31T12:30:00Z"), "at [TIMESTAMP]");
+    /// ```
+    pub fn insert_preset(&mut self, preset: super::Preset) -> crate::assert::Result<()> {
+        self.insert(preset.placeholder(), preset)
+    }
+
+    /// Replace the structured data value at `path`, whatever it is, with `placeholder`
+    ///
+    /// `path` is a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) where a `*` segment
+    /// matches every element of an array or every value of an object.  Paths that are not present
+    /// in the data are ignored.
+    ///
+    /// Like [`Data::select`][crate::Data::select], JSON Lines are treated as an array of lines, so
+    /// `/*/id` matches `id` in every line.
+    ///
+    /// `placeholder` must be enclosed in `[` and `]`.
+    ///
+    /// ```rust
+    /// let mut subst = snapbox::Redactions::new();
+    /// subst.insert_at("/created_at", "[TIMESTAMP]");
+    /// subst.insert_at("/items/*/id", "[ID]");
+    /// ```
+    #[cfg(feature = "structured-data")]
+    pub fn insert_at(
+        &mut self,
+        path: &str,
+        placeholder: &'static str,
+    ) -> crate::assert::Result<()> {
+        let placeholder = validate_placeholder(placeholder)?;
+        let path = RedactedPath::parse(path, placeholder)?;
+        self.paths.get_or_insert(Vec::new()).push(path);
+        Ok(())
+    }
+
     /// Insert additional match patterns
     ///
     /// Placeholders must be enclosed in `[` and `]`.
@@ -104,6 +159,10 @@ impl Redactions {
                 placeholders.retain(|p| *p != placeholder);
                 !placeholders.is_empty()
             });
+        #[cfg(feature = "structured-data")]
+        if let Some(paths) = self.paths.as_mut() {
+            paths.retain(|path| path.placeholder != placeholder);
+        }
         Ok(())
     }
 
@@ -118,9 +177,15 @@ impl Redactions {
     /// assert_eq!(output, "Hello [LOCATION]!");
     /// ```
     pub fn redact(&self, input: &str) -> String {
+        self.redact_numbered(input, &mut Backrefs::default())
+    }
+
+    /// Apply redaction, numbering the placeholders tracked in `backrefs`
+    pub(crate) fn redact_numbered(&self, input: &str, backrefs: &mut Backrefs) -> String {
         let mut input = input.to_owned();
         replace_many(
             &mut input,
+            backrefs,
             self.vars
                 .iter()
                 .flatten()
@@ -133,6 +198,14 @@ impl Redactions {
         input
     }
 
+    /// Replace the values at paths registered with [`Redactions::insert_at`]
+    #[cfg(feature = "structured-data")]
+    pub(crate) fn redact_paths(&self, value: &mut serde_json::Value) {
+        for path in self.paths.iter().flatten() {
+            path.redact(value, &path.segments);
+        }
+    }
+
     /// Clear unused redactions from expected data
     ///
     /// Some redactions can be conditionally present, like redacting [`std::env::consts::EXE_SUFFIX`].
@@ -143,6 +216,7 @@ impl Redactions {
             let mut pattern = pattern.to_owned();
             replace_many(
                 &mut pattern,
+                &mut Backrefs::default(),
                 self.unused.iter().flatten().map(|var| (var, "")),
             );
             Cow::Owned(pattern)
@@ -152,6 +226,50 @@ impl Redactions {
     }
 }
 
+/// Distinct values redacted by placeholders that are used as back-references, like `[ID#1]`
+#[derive(Default, Debug)]
+pub(crate) struct Backrefs {
+    values: std::collections::BTreeMap<String, Vec<String>>,
+}
+
+impl Backrefs {
+    /// Track each placeholder that `pattern` uses as a back-reference
+    pub(crate) fn from_pattern(pattern: &str) -> Self {
+        let mut backrefs = Self::default();
+        let mut rest = pattern;
+        while let Some(start) = rest.find('[') {
+            rest = &rest[start + 1..];
+            let Some(end) = rest.find(']') else {
+                break;
+            };
+            let Some((name, number)) = rest[..end].split_once('#') else {
+                continue;
+            };
+            if is_placeholder_name(name)
+                && !number.is_empty()
+                && number.bytes().all(|b| b.is_ascii_digit())
+            {
+                backrefs.values.entry(format!("[{name}]")).or_default();
+            }
+        }
+        backrefs
+    }
+
+    fn replacement<'p>(&mut self, placeholder: &'p str, value: &str) -> Cow<'p, str> {
+        let Some(values) = self.values.get_mut(placeholder) else {
+            return Cow::Borrowed(placeholder);
+        };
+        let index = if let Some(index) = values.iter().position(|v| v == value) {
+            index
+        } else {
+            values.push(value.to_owned());
+            values.len() - 1
+        };
+        let name = &placeholder[..placeholder.len() - 1];
+        Cow::Owned(format!("{name}#{}]", index + 1))
+    }
+}
+
 #[derive(Clone)]
 pub struct RedactedValue {
     inner: Option<RedactedValueInner>,
@@ -167,6 +285,7 @@ enum RedactedValueInner {
     },
     #[cfg(feature = "regex")]
     Regex(regex::Regex),
+    Preset(super::Preset),
 }
 
 impl RedactedValueInner {
@@ -194,6 +313,7 @@ impl RedactedValueInner {
                 let m = captures.name("redacted").or_else(|| captures.get(0))?;
                 Some(m.range())
             }
+            Self::Preset(p) => p.find_in(buffer),
         }
     }
 
@@ -207,6 +327,10 @@ impl RedactedValueInner {
                 let s = r.as_str();
                 (1, std::cmp::Reverse(s.len()), s)
             }
+            Self::Preset(p) => {
+                let s = p.placeholder();
+                (2, std::cmp::Reverse(s.len()), s)
+            }
         }
     }
 }
@@ -295,6 +419,14 @@ impl From<&'_ regex::Regex> for RedactedValue {
     }
 }
 
+impl From<super::Preset> for RedactedValue {
+    fn from(inner: super::Preset) -> Self {
+        Self {
+            inner: Some(RedactedValueInner::Preset(inner)),
+        }
+    }
+}
+
 impl PartialOrd for RedactedValueInner {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -318,18 +450,85 @@ impl Eq for RedactedValueInner {}
 /// Replacements is `(from, to)`
 fn replace_many<'a>(
     buffer: &mut String,
+    backrefs: &mut Backrefs,
     replacements: impl IntoIterator<Item = (&'a RedactedValueInner, &'a str)>,
 ) {
     for (var, replace) in replacements {
         let mut index = 0;
         while let Some(offset) = var.find_in(&buffer[index..]) {
             let old_range = (index + offset.start)..(index + offset.end);
-            buffer.replace_range(old_range, replace);
+            let replace = backrefs.replacement(replace, &buffer[old_range.clone()]);
+            buffer.replace_range(old_range, &replace);
             index += offset.start + replace.len();
         }
     }
 }
 
+#[cfg(feature = "structured-data")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct RedactedPath {
+    segments: Vec<String>,
+    placeholder: &'static str,
+}
+
+#[cfg(feature = "structured-data")]
+impl RedactedPath {
+    const WILDCARD: &'static str = "*";
+
+    fn parse(path: &str, placeholder: &'static str) -> crate::assert::Result<Self> {
+        let Some(rest) = path.strip_prefix('/') else {
+            return Err(format!("Path `{path}` must start with `/`").into());
+        };
+        let segments = rest
+            .split('/')
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        Ok(Self {
+            segments,
+            placeholder,
+        })
+    }
+
+    fn redact(&self, value: &mut serde_json::Value, segments: &[String]) {
+        let Some((segment, rest)) = segments.split_first() else {
+            *value = serde_json::Value::String(self.placeholder.to_owned());
+            return;
+        };
+        match value {
+            serde_json::Value::Array(array) if segment == Self::WILDCARD => {
+                for value in array {
+                    self.redact(value, rest);
+                }
+            }
+            serde_json::Value::Object(object) if segment == Self::WILDCARD => {
+                for value in object.values_mut() {
+                    self.redact(value, rest);
+                }
+            }
+            serde_json::Value::Array(array) => {
+                if let Some(value) = segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| array.get_mut(index))
+                {
+                    self.redact(value, rest);
+                }
+            }
+            serde_json::Value::Object(object) => {
+                if let Some(value) = object.get_mut(segment) {
+                    self.redact(value, rest);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `name` can be the name of a placeholder, like `UUID` in `[UUID]`
+pub(crate) fn is_placeholder_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_uppercase() || b == b'_')
+}
+
 fn validate_placeholder(placeholder: &'static str) -> crate::assert::Result<&'static str> {
     if !placeholder.starts_with('[') || !placeholder.ends_with(']') {
         return Err(format!("Key `{placeholder}` is not enclosed in []").into());
@@ -364,4 +563,11 @@ mod test {
             assert_eq!(expected, actual, "placeholder={placeholder:?}");
         }
     }
+
+    #[test]
+    fn test_backrefs_from_pattern() {
+        let backrefs = Backrefs::from_pattern("[A#1] [B_C#12] [D] [e#1] [F#] [#1] [G#x] [A#2]");
+        let actual = backrefs.values.keys().collect::<Vec<_>>();
+        assert_eq!(actual, ["[A]", "[B_C]"]);
+    }
 }
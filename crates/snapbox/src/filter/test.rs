@@ -1,7 +1,6 @@
 #[cfg(feature = "json")]
 use serde_json::json;
 
-#[cfg(feature = "json")]
 use super::*;
 
 // Tests for normalization on json
@@ -118,3 +117,44 @@ fn json_normalize_paths_and_lines_array_obj() {
     });
     assert_eq!(Data::json(new_lines), data);
 }
+
+#[test]
+fn str_strip_ansi() {
+    let cases = [
+        ("\x1b[1m\x1b[32mok\x1b[0m", "ok"),
+        ("\x1b[38;5;208mwarn\x1b[m: x", "warn: x"),
+        (
+            "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07 text",
+            "link text",
+        ),
+        ("\x1b(Bplain", "plain"),
+        ("unterminated \x1b[31", "unterminated "),
+        ("no escapes\n", "no escapes\n"),
+    ];
+    for (input, expected) in cases {
+        let actual = strip_ansi(input);
+        assert_eq!(actual, expected, "{input:?}");
+    }
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_strip_ansi() {
+    let json = json!({
+        "\x1b[1mname\x1b[0m": ["\x1b[31merror\x1b[0m", 1],
+    });
+    let data = Data::json(json);
+    let data = FilterStripAnsi.filter(data);
+    let expected = json!({
+        "name": ["error", 1],
+    });
+    assert_eq!(Data::json(expected), data);
+}
+
+#[test]
+fn str_whitespace() {
+    let input = "name \t  value  \n\n  \t\nlast\t";
+    assert_eq!(trim_trailing_whitespace(input), "name \t  value\n\n\nlast");
+    assert_eq!(collapse_spaces(input), "name value \n\n \nlast ");
+    assert_eq!(remove_blank_lines(input), "name \t  value  \nlast\t");
+}
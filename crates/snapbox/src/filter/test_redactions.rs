@@ -198,6 +198,108 @@ b: [B]";
     assert_eq!(actual, pattern.into_data());
 }
 
+#[test]
+fn str_normalize_user_preset() {
+    let input = "\
+[2024-01-31T12:30:00.123Z] worker started (pid=4321)
+[2024-01-31T12:30:01.456Z] finished in 1.33s";
+    let pattern = "\
+[[TIMESTAMP]] worker started (pid=[PID])
+[[TIMESTAMP]] finished in [DURATION]";
+    let mut sub = Redactions::new();
+    sub.insert_preset(Preset::Timestamp).unwrap();
+    sub.insert_preset(Preset::Pid).unwrap();
+    sub.insert_preset(Preset::Duration).unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(input.into(), &pattern.into());
+    assert_eq!(actual, pattern.into_data());
+}
+
+#[test]
+fn str_normalize_user_preset_custom_placeholder() {
+    let input = "started at 2024-01-31T12:30:00Z";
+    let pattern = "started at [STARTED]";
+    let mut sub = Redactions::new();
+    sub.insert("[STARTED]", Preset::Timestamp).unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(input.into(), &pattern.into());
+    assert_eq!(actual, pattern.into_data());
+}
+
+#[test]
+fn str_normalize_user_backref() {
+    let input = "\
+created 67e55044-10b1-426f-9247-bb680e5fe0c8
+created 936da01f-9abd-4d9d-80c7-02af85c822a8
+deleted 67e55044-10b1-426f-9247-bb680e5fe0c8";
+    let pattern = "\
+created [UUID#1]
+created [UUID#2]
+deleted [UUID#1]";
+    let mut sub = Redactions::new();
+    sub.insert_preset(Preset::Uuid).unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(input.into(), &pattern.into());
+    assert_eq!(actual, pattern.into_data());
+}
+
+#[test]
+fn str_normalize_user_backref_mismatch() {
+    let input = "\
+created 67e55044-10b1-426f-9247-bb680e5fe0c8
+deleted 936da01f-9abd-4d9d-80c7-02af85c822a8
+done in [UUID]";
+    let pattern = "\
+created [UUID#1]
+deleted [UUID#1]
+done in [UUID]";
+    let expected = "\
+created [UUID#1]
+deleted [UUID#2]
+done in [UUID]";
+    let mut sub = Redactions::new();
+    sub.insert_preset(Preset::Uuid).unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(input.into(), &pattern.into());
+    assert_eq!(actual, expected.into_data());
+}
+
+#[test]
+fn str_normalize_user_backref_unnumbered() {
+    let input = "\
+created 67e55044-10b1-426f-9247-bb680e5fe0c8
+deleted 936da01f-9abd-4d9d-80c7-02af85c822a8";
+    let pattern = "\
+created [UUID]
+deleted [UUID]";
+    let mut sub = Redactions::new();
+    sub.insert_preset(Preset::Uuid).unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(input.into(), &pattern.into());
+    assert_eq!(actual, pattern.into_data());
+}
+
+#[test]
+fn str_normalize_user_backref_mixed() {
+    let input = "\
+created 67e55044-10b1-426f-9247-bb680e5fe0c8 by 936da01f-9abd-4d9d-80c7-02af85c822a8
+deleted 67e55044-10b1-426f-9247-bb680e5fe0c8 by 67e55044-10b1-426f-9247-bb680e5fe0c8";
+    let pattern = "\
+created [UUID#1] by [UUID]
+deleted [UUID#1] by [UUID]";
+    let mut sub = Redactions::new();
+    sub.insert_preset(Preset::Uuid).unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(input.into(), &pattern.into());
+    assert_eq!(actual, pattern.into_data());
+}
+
 #[test]
 fn str_normalize_user_disabled() {
     let input = "cargo";
@@ -572,3 +674,344 @@ fn json_normalize_glob_obj_key() {
     let expected_actual = Data::json(expected_actual);
     assert_eq!(actual, expected_actual);
 }
+
+#[test]
+#[cfg(feature = "json")]
+fn json_normalize_backref() {
+    let expected = json!({
+        "a": {"id": "[ID#1]", "parent": null},
+        "b": {"id": "[ID#2]", "parent": "[ID#1]"},
+    });
+    let expected = Data::json(expected);
+    let actual = json!({
+        "a": {"id": "x-1", "parent": null},
+        "b": {"id": "x-2", "parent": "x-2"},
+    });
+    let actual = Data::json(actual);
+    let mut sub = Redactions::new();
+    sub.insert("[ID]", "x-1").unwrap();
+    sub.insert("[ID]", "x-2").unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(actual, &expected);
+
+    let expected_actual = json!({
+        "a": {"id": "[ID#1]", "parent": null},
+        "b": {"id": "[ID#2]", "parent": "[ID#2]"},
+    });
+    let expected_actual = Data::json(expected_actual);
+    assert_eq!(actual, expected_actual);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_normalize_typed_wildcard() {
+    let expected = json!({
+        "count": "[..:int] items",
+        "hash": "[..:hex]",
+        "file": "[..:path]",
+        "status": "[..:int] items",
+    });
+    let expected = Data::json(expected);
+    let actual = json!({
+        "count": "42 items",
+        "hash": "c0ffee",
+        "file": "target/debug/foo",
+        "status": "error: no items",
+    });
+    let actual = Data::json(actual);
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .normalize(actual, &expected);
+
+    let expected_actual = json!({
+        "count": "[..:int] items",
+        "hash": "[..:hex]",
+        "file": "[..:path]",
+        "status": "error: no items",
+    });
+    let expected_actual = Data::json(expected_actual);
+    assert_eq!(actual, expected_actual);
+}
+
+#[test]
+fn str_normalize_typed_wildcard() {
+    let input = "\
+Compiled 12 crates in 3.5s
+Wrote target/out.bin";
+    let pattern = "\
+Compiled [..:int] crates in [..:float]s
+Wrote [..:path]";
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .normalize(input.into(), &pattern.into());
+    assert_eq!(actual, pattern.into_data());
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_normalize_approx_placeholder() {
+    let expected = json!({
+        "a": "{~1.5}",
+        "b": "{~2.5±0.01}",
+        "c": "{~3.5±0.01}",
+        "d": "{~4.5}",
+    });
+    let expected = Data::json(expected);
+    let actual = json!({
+        "a": 1.5000000000000002,
+        "b": 2.505,
+        "c": 3.6,
+        "d": "4.5",
+    });
+    let actual = Data::json(actual);
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .normalize(actual, &expected);
+
+    let expected_actual = json!({
+        "a": "{~1.5}",
+        "b": "{~2.5±0.01}",
+        "c": 3.6,
+        "d": "4.5",
+    });
+    let expected_actual = Data::json(expected_actual);
+    assert_eq!(actual, expected_actual);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_normalize_float_tolerance() {
+    let expected = json!({
+        "a": 0.3,
+        "b": [1, 2.0],
+        "c": "{~0.5}",
+        "d": 0.7,
+    });
+    let expected = Data::json(expected);
+    let actual = json!({
+        "a": 0.1 + 0.2,
+        "b": [1.0000000001, 2],
+        "c": 0.5000000001,
+        "d": 0.8,
+    });
+    let actual = Data::json(actual);
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .float_tolerance(1e-9)
+        .normalize(actual, &expected);
+
+    let expected_actual = json!({
+        "a": 0.3,
+        "b": [1, 2.0],
+        "c": "{~0.5}",
+        "d": 0.8,
+    });
+    let expected_actual = Data::json(expected_actual);
+    assert_eq!(actual, expected_actual);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_normalize_without_float_tolerance() {
+    let expected = json!({"a": 0.3});
+    let expected = Data::json(expected);
+    let actual = json!({"a": 0.1 + 0.2});
+    let actual = Data::json(actual);
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .normalize(actual, &expected);
+
+    let expected_actual = json!({"a": 0.1 + 0.2});
+    let expected_actual = Data::json(expected_actual);
+    assert_eq!(actual, expected_actual);
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn yaml_normalize_glob_obj_key() {
+    let expected = "
+a: value-a
+c: '[..]-c'
+people: '{...}'
+'...': '{...}'
+"
+    .is_yaml();
+    let actual = "
+a: value-a
+b: value-b
+c: value-c
+people:
+- name: JohnDoe
+"
+    .is_yaml();
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .normalize(actual, &expected);
+
+    assert_eq!(actual.format(), crate::data::DataFormat::Yaml);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn toml_normalize_glob_obj_key() {
+    let expected = r#"
+[package]
+name = "foo"
+version = "{...}"
+"..." = "{...}"
+
+[dependencies]
+bar = "[..]"
+"#
+    .is_toml();
+    let actual = r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+bar = "1.0"
+"#
+    .is_toml();
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .normalize(actual, &expected);
+
+    assert_eq!(actual.format(), crate::data::DataFormat::Toml);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn toml_normalize_datetime_redaction() {
+    let expected = r#"
+name = "foo"
+when = "[TIMESTAMP]"
+"#
+    .is_toml();
+    let actual = r#"
+name = "bar"
+when = 2024-01-01T00:00:00Z
+"#
+    .is_toml();
+    let mut sub = Redactions::new();
+    sub.insert_preset(Preset::Timestamp).unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(actual, &expected);
+
+    assert_eq!(actual.format(), crate::data::DataFormat::Toml);
+    assert_eq!(
+        actual.render().unwrap(),
+        "name = \"bar\"\nwhen = \"[TIMESTAMP]\"\n"
+    );
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn csv_normalize_cell_redactions() {
+    let expected = "
+name,count
+foo,[..]
+...
+baz,\"multi
+[..]\"
+"
+    .is_csv();
+    let actual = "
+name,count
+foo,10
+bar,11
+baz,\"multi
+line\"
+"
+    .is_csv();
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .normalize(actual, &expected);
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn csv_normalize_cell_mismatch() {
+    let expected = "
+name,count
+foo,[..]
+bar,12
+"
+    .is_csv();
+    let actual = "
+name,count
+foo,10
+bar,11
+"
+    .is_csv();
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .normalize(actual, &expected);
+
+    let expected_actual = "
+name,count
+foo,[..]
+bar,11
+"
+    .is_csv();
+    assert_eq!(actual, expected_actual);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_normalize_redact_at() {
+    let expected = json!({
+        "created_at": "[TIMESTAMP]",
+        "items": [
+            {"id": "[ID]", "name": "a"},
+            {"id": "[ID]", "name": "b"},
+        ],
+        "enabled": true,
+    });
+    let expected = Data::json(expected);
+    let actual = json!({
+        "created_at": 1700000000,
+        "items": [
+            {"id": 17, "name": "a"},
+            {"id": false, "name": "b"},
+        ],
+        "enabled": true,
+    });
+    let actual = Data::json(actual);
+    let mut sub = Redactions::new();
+    sub.insert_at("/created_at", "[TIMESTAMP]").unwrap();
+    sub.insert_at("/items/*/id", "[ID]").unwrap();
+    sub.insert_at("/missing/path", "[ID]").unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(actual, &expected);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn jsonlines_normalize_redact_at() {
+    let expected = Data::jsonlines(vec![json!({"pid": "[PID]"}), json!({"pid": "[PID]"})]);
+    let actual = Data::jsonlines(vec![json!({"pid": 101}), json!({"pid": 102})]);
+    let mut sub = Redactions::new();
+    sub.insert_at("/*/pid", "[PID]").unwrap();
+    let actual = NormalizeToExpected::new()
+        .redact_with(&sub)
+        .normalize(actual, &expected);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn redact_at_invalid_path() {
+    let mut sub = Redactions::new();
+    assert!(sub.insert_at("created_at", "[TIMESTAMP]").is_err());
+    assert!(sub.insert_at("/created_at", "TIMESTAMP").is_err());
+}
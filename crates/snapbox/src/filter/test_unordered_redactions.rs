@@ -624,3 +624,29 @@ fn json_normalize_glob_obj_key() {
     let expected_actual = Data::json(expected_actual);
     assert_eq!(actual, expected_actual);
 }
+
+#[test]
+#[cfg(feature = "csv")]
+fn csv_normalize_unordered_multiline_cells() {
+    let expected = "
+\"multi
+line\",[..]
+name,1
+...
+"
+    .is_csv()
+    .unordered();
+    let actual = "
+name,1
+other,3
+\"multi
+line\",2
+"
+    .is_csv();
+    let actual = NormalizeToExpected::new()
+        .redact()
+        .unordered()
+        .normalize(actual, &expected);
+
+    assert_eq!(actual, expected);
+}
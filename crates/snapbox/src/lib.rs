@@ -59,6 +59,15 @@
 //! ```
 //!
 //! To update the snapshot, run the tests with `SNAPSHOTS=overwrite` set.
+//! This is refused in CI, see [`is_overwrite_allowed`][assert::is_overwrite_allowed].
+//! To review updates before applying them, use `SNAPSHOTS=review` and see
+//! [`PendingSnapshot`][data::PendingSnapshot].
+//! To find snapshots no longer used by any test, use `SNAPSHOTS=prune` and see
+//! [`unused_snapshots`][data::unused_snapshots].
+//! For a machine-readable record of each comparison, like `JUnit` XML for CI, set
+//! `SNAPSHOTS_REPORT` to a file path and see [`Report`][report::Report].
+//! To get each mismatched snapshot file as a patch for `git apply`, set `SNAPSHOTS_PATCH_DIR` to a
+//! directory and see [`PatchDir`][report::PatchDir].
 //!
 //! # Feature flags
 //!
@@ -124,6 +133,9 @@ pub fn assert_subset_eq(
 /// Pattern syntax:
 /// - `...` is a line-wildcard when on a line by itself
 /// - `[..]` is a character-wildcard when inside a line
+/// - `[..:int]`, `[..:float]`, `[..:word]`, `[..:path]` and `[..:hex]` are character-wildcards
+///   limited to that kind of value
+/// - `[~1.5]` or `[~1.5±0.01]` matches a number within a tolerance
 /// - `[EXE]` matches `.exe` on Windows
 ///
 /// Normalization:
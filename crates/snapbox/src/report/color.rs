@@ -6,11 +6,14 @@ pub struct Palette {
     pub(crate) hint: Style,
     pub(crate) expected: Style,
     pub(crate) actual: Style,
+    pub(crate) layout: super::DiffLayout,
+    pub(crate) diff_options: super::DiffOptions,
 }
 
 impl Palette {
+    /// Style output, unless stderr does not support colors
     pub fn color() -> Self {
-        if cfg!(feature = "color") {
+        if is_color_supported() {
             Self {
                 info: anstyle::AnsiColor::Green.on_default(),
                 warn: anstyle::AnsiColor::Yellow.on_default(),
@@ -18,6 +21,8 @@ impl Palette {
                 hint: anstyle::Effects::DIMMED.into(),
                 expected: anstyle::AnsiColor::Red.on_default() | anstyle::Effects::UNDERLINE,
                 actual: anstyle::AnsiColor::Green.on_default() | anstyle::Effects::UNDERLINE,
+                layout: Default::default(),
+                diff_options: Default::default(),
             }
         } else {
             Self::plain()
@@ -28,6 +33,24 @@ impl Palette {
         Self::default()
     }
 
+    /// Override how [`write_diff`][crate::report::write_diff] lays out text differences
+    pub fn diff_layout(mut self, layout: super::DiffLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Override how much of a text difference [`write_diff`][crate::report::write_diff] shows
+    pub fn diff_options(mut self, options: super::DiffOptions) -> Self {
+        self.diff_options = options;
+        self
+    }
+
+    /// Whether differences can only be shown through text
+    #[cfg(feature = "diff")]
+    pub(crate) fn is_plain(&self) -> bool {
+        self.expected == Style::new() && self.actual == Style::new()
+    }
+
     pub fn info<D: std::fmt::Display>(self, item: D) -> Styled<D> {
         Styled::new(item, self.info)
     }
@@ -53,6 +76,16 @@ impl Palette {
     }
 }
 
+#[cfg(feature = "color")]
+fn is_color_supported() -> bool {
+    anstream::AutoStream::choice(&std::io::stderr()) != anstream::ColorChoice::Never
+}
+
+#[cfg(not(feature = "color"))]
+fn is_color_supported() -> bool {
+    false
+}
+
 pub(crate) use anstyle::Style;
 
 #[derive(Debug)]
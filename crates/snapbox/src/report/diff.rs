@@ -1,5 +1,138 @@
 use crate::report::Styled;
 
+/// Default environment variable for selecting a [`DiffLayout`], see [`DiffLayout::with_env_value`]
+pub const DEFAULT_DIFF_ENV: &str = "SNAPSHOTS_DIFF";
+
+/// How [`write_diff`] lays out text differences, see [`Palette::diff_layout`][crate::report::Palette::diff_layout]
+///
+/// CSV and TSV are always reported record by record, whatever the layout.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DiffLayout {
+    /// Removed lines followed by added lines
+    #[default]
+    Unified,
+    /// Expected and actual lines next to each other, wrapped to fit `COLUMNS` (default: 120)
+    SideBySide,
+    /// For JSON, YAML, and TOML, each changed value by its path (`/items/3/name: "a" → "b"`)
+    ///
+    /// Other data, or values that only differ in formatting, fall back to [`DiffLayout::Unified`].
+    Structural,
+}
+
+impl DiffLayout {
+    /// Read the layout from an environment variable
+    ///
+    /// Returns `None` when it is unset or not valid, see [`DiffLayout::with_env_value`]
+    pub fn with_env_var(var: impl AsRef<std::ffi::OsStr>) -> Option<Self> {
+        let value = std::env::var_os(var)?;
+        Self::with_env_value(value)
+    }
+
+    /// Parse `unified`, `side-by-side`, or `structural`
+    ///
+    /// Returns `None` for any other value.
+    pub fn with_env_value(value: impl AsRef<std::ffi::OsStr>) -> Option<Self> {
+        match value.as_ref().to_str()? {
+            "unified" => Some(Self::Unified),
+            "side-by-side" => Some(Self::SideBySide),
+            "structural" => Some(Self::Structural),
+            _ => None,
+        }
+    }
+}
+
+/// Default environment variable for [`DiffOptions`], like `context=10,elide=never,timeout=5000`
+pub const DEFAULT_DIFF_OPTIONS_ENV: &str = "SNAPSHOTS_DIFF_OPTIONS";
+
+/// How much of a text difference [`write_diff`] shows, see [`Palette::diff_options`][crate::report::Palette::diff_options]
+///
+/// For CSV and TSV, this counts records rather than lines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DiffOptions {
+    context: usize,
+    min_elide: Option<usize>,
+    timeout: std::time::Duration,
+}
+
+impl DiffOptions {
+    pub fn new() -> Self {
+        Self {
+            context: 5,
+            min_elide: Some(20),
+            timeout: std::time::Duration::from_millis(500),
+        }
+    }
+
+    /// Unchanged lines to show around each change (default: 5)
+    pub fn context(mut self, lines: usize) -> Self {
+        self.context = lines;
+        self
+    }
+
+    /// Only leave out unchanged lines when the diff is longer than this (default: 20)
+    pub fn min_elide(mut self, lines: usize) -> Self {
+        self.min_elide = Some(lines);
+        self
+    }
+
+    /// Show every unchanged line
+    pub fn never_elide(mut self) -> Self {
+        self.min_elide = None;
+        self
+    }
+
+    /// Stop looking for the smallest diff after this long (default: 500ms)
+    ///
+    /// The diff is still correct but may show more lines as changed than needed.
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Read the options from an environment variable
+    ///
+    /// Returns `None` when it is unset or not valid, see [`DiffOptions::with_env_value`]
+    pub fn with_env_var(var: impl AsRef<std::ffi::OsStr>) -> Option<Self> {
+        let value = std::env::var_os(var)?;
+        Self::with_env_value(value)
+    }
+
+    /// Parse comma-separated settings, like `context=10,elide=never,timeout=5000`
+    ///
+    /// - `context=<lines>`
+    /// - `elide=<lines>` or `elide=never`, see [`DiffOptions::min_elide`]
+    /// - `timeout=<milliseconds>`
+    ///
+    /// Settings left out keep their default.  Returns `None` if any setting is not valid.
+    pub fn with_env_value(value: impl AsRef<std::ffi::OsStr>) -> Option<Self> {
+        let mut options = Self::new();
+        for setting in value.as_ref().to_str()?.split(',') {
+            let setting = setting.trim();
+            if setting.is_empty() {
+                continue;
+            }
+            let (key, value) = setting.split_once('=')?;
+            options = match (key.trim(), value.trim()) {
+                ("context", lines) => options.context(lines.parse().ok()?),
+                ("elide", "never") => options.never_elide(),
+                ("elide", lines) => options.min_elide(lines.parse().ok()?),
+                ("timeout", millis) => {
+                    options.timeout(std::time::Duration::from_millis(millis.parse().ok()?))
+                }
+                _ => return None,
+            };
+        }
+        Some(options)
+    }
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub fn write_diff(
     writer: &mut dyn std::fmt::Write,
     expected: &crate::Data,
@@ -8,6 +141,46 @@ pub fn write_diff(
     actual_name: Option<&dyn std::fmt::Display>,
     palette: crate::report::Palette,
 ) -> Result<(), std::fmt::Error> {
+    #[cfg(all(feature = "diff", feature = "csv"))]
+    match (&expected.inner.value, &actual.inner.value) {
+        (
+            crate::data::DataValue::Csv(expected_records),
+            crate::data::DataValue::Csv(actual_records),
+        )
+        | (
+            crate::data::DataValue::Tsv(expected_records),
+            crate::data::DataValue::Tsv(actual_records),
+        ) => {
+            return write_records_diff(
+                writer,
+                expected_records,
+                actual_records,
+                expected_name,
+                actual_name,
+                palette,
+            );
+        }
+        _ => {}
+    }
+
+    #[cfg(feature = "structured-data")]
+    if palette.layout == DiffLayout::Structural {
+        if let (Some(expected_value), Some(actual_value)) =
+            (structured_value(expected), structured_value(actual))
+        {
+            if super::structural::write_structural_diff(
+                writer,
+                expected_value,
+                actual_value,
+                expected_name,
+                actual_name,
+                palette,
+            )? {
+                return Ok(());
+            }
+        }
+    }
+
     #[allow(unused_mut)]
     let mut rendered = false;
     #[cfg(feature = "diff")]
@@ -51,6 +224,20 @@ pub fn write_diff(
             actual_line_offset,
         )?;
         rendered = true;
+    } else if let (Some(expected), Some(actual)) = (expected.hexdump(), actual.hexdump()) {
+        let expected_line_offset = 0;
+        let actual_line_offset = 0;
+        write_diff_inner(
+            writer,
+            &expected,
+            &actual,
+            expected_name,
+            actual_name,
+            palette,
+            expected_line_offset,
+            actual_line_offset,
+        )?;
+        rendered = true;
     }
 
     if !rendered {
@@ -82,78 +269,102 @@ fn write_diff_inner(
     expected_line_offset: usize,
     actual_line_offset: usize,
 ) -> Result<(), std::fmt::Error> {
-    let timeout = std::time::Duration::from_millis(500);
-    let min_elide = 20;
-    let context = 5;
+    let width = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(120);
+    write_text_diff(
+        writer,
+        expected,
+        actual,
+        expected_name,
+        actual_name,
+        palette,
+        expected_line_offset,
+        actual_line_offset,
+        width,
+    )
+}
+
+#[cfg(feature = "diff")]
+#[allow(clippy::too_many_arguments)]
+fn write_text_diff(
+    writer: &mut dyn std::fmt::Write,
+    expected: &str,
+    actual: &str,
+    expected_name: Option<&dyn std::fmt::Display>,
+    actual_name: Option<&dyn std::fmt::Display>,
+    palette: crate::report::Palette,
+    expected_line_offset: usize,
+    actual_line_offset: usize,
+    width: usize,
+) -> Result<(), std::fmt::Error> {
+    let timeout = palette.diff_options.timeout;
 
+    let start = std::time::Instant::now();
     let changes = similar::TextDiff::configure()
         .algorithm(similar::Algorithm::Patience)
         .timeout(timeout)
         .newline_terminated(false)
         .diff_lines(expected, actual);
 
-    writeln!(writer)?;
-    if let Some(expected_name) = expected_name {
+    write_header(writer, expected_name, actual_name, palette)?;
+    if timeout <= start.elapsed() {
         writeln!(
             writer,
             "{}",
-            palette.error(format_args!("{:->4} expected: {}", "", expected_name))
+            palette.hint(format_args!(
+                "Diff took over {timeout:?} and may not be minimal, raise it with {DEFAULT_DIFF_OPTIONS_ENV}=timeout=<milliseconds>"
+            ))
         )?;
-    } else {
-        writeln!(writer, "{}", palette.error(format_args!("--- Expected")))?;
     }
-    if let Some(actual_name) = actual_name {
-        writeln!(
-            writer,
-            "{}",
-            palette.info(format_args!("{:+>4} actual:   {}", "", actual_name))
-        )?;
-    } else {
-        writeln!(writer, "{}", palette.info(format_args!("+++ Actual")))?;
-    }
-    let changes = changes
+    let deadline = std::time::Instant::now() + timeout;
+    let mut by_word = similar::InlineChangeOptions::new();
+    by_word
+        .mode(similar::InlineChangeMode::Words)
+        .semantic_cleanup(true);
+    let mut by_char = by_word;
+    // Characters match by coincidence more than words do
+    by_char
+        .mode(similar::InlineChangeMode::Chars)
+        .min_ratio(0.7);
+    let ops = changes
         .ops()
         .iter()
-        .flat_map(|op| changes.iter_inline_changes(op))
-        .collect::<Vec<_>>();
-    let tombstones = if min_elide < changes.len() {
-        let mut tombstones = vec![true; changes.len()];
-
-        let mut counter = context;
-        for (i, change) in changes.iter().enumerate() {
-            match change.tag() {
-                similar::ChangeTag::Insert | similar::ChangeTag::Delete => {
-                    counter = context;
-                    tombstones[i] = false;
-                }
-                similar::ChangeTag::Equal => {
-                    if counter != 0 {
-                        tombstones[i] = false;
-                        counter -= 1;
-                    }
+        .map(|op| {
+            // Lines without whitespace, like minified JSON, are a single word
+            for options in [by_word, by_char] {
+                let inline = changes
+                    .iter_inline_changes_with_options_deadline(op, options, Some(deadline))
+                    .collect::<Vec<_>>();
+                if op.tag() != similar::DiffTag::Replace || is_partially_emphasized(&inline) {
+                    return inline;
                 }
             }
-        }
+            changes
+                .iter_changes(op)
+                .map(similar::InlineChange::from)
+                .collect()
+        })
+        .collect::<Vec<_>>();
 
-        let mut counter = context;
-        for (i, change) in changes.iter().enumerate().rev() {
-            match change.tag() {
-                similar::ChangeTag::Insert | similar::ChangeTag::Delete => {
-                    counter = context;
-                    tombstones[i] = false;
-                }
-                similar::ChangeTag::Equal => {
-                    if counter != 0 {
-                        tombstones[i] = false;
-                        counter -= 1;
-                    }
-                }
-            }
-        }
-        tombstones
-    } else {
-        Vec::new()
-    };
+    if palette.layout == DiffLayout::SideBySide {
+        return write_side_by_side(
+            writer,
+            ops,
+            palette,
+            expected_line_offset,
+            actual_line_offset,
+            width,
+        );
+    }
+
+    let changes = ops.into_iter().flatten().collect::<Vec<_>>();
+    let is_changed = changes
+        .iter()
+        .map(|change| change.tag() != similar::ChangeTag::Equal)
+        .collect::<Vec<_>>();
+    let tombstones = elide(&is_changed, palette.diff_options);
 
     let mut elided = false;
     for (i, change) in changes.into_iter().enumerate() {
@@ -212,6 +423,509 @@ fn write_diff_inner(
     Ok(())
 }
 
+/// Mark unchanged lines far enough from changes to be left out
+///
+/// Nothing is elided when there are few lines.
+#[cfg(feature = "diff")]
+fn elide(is_changed: &[bool], options: DiffOptions) -> Vec<bool> {
+    let context = options.context;
+    let Some(min_elide) = options.min_elide else {
+        return Vec::new();
+    };
+
+    if is_changed.len() <= min_elide {
+        return Vec::new();
+    }
+    let mut tombstones = vec![true; is_changed.len()];
+
+    let mut counter = context;
+    for (i, is_changed) in is_changed.iter().enumerate() {
+        if *is_changed {
+            counter = context;
+            tombstones[i] = false;
+        } else if counter != 0 {
+            tombstones[i] = false;
+            counter -= 1;
+        }
+    }
+
+    let mut counter = context;
+    for (i, is_changed) in is_changed.iter().enumerate().rev() {
+        if *is_changed {
+            counter = context;
+            tombstones[i] = false;
+        } else if counter != 0 {
+            tombstones[i] = false;
+            counter -= 1;
+        }
+    }
+    tombstones
+}
+
+/// A line of [`write_side_by_side`]
+#[cfg(feature = "diff")]
+enum Row<'s> {
+    Equal(similar::InlineChange<'s, str>),
+    Changed(
+        Option<similar::InlineChange<'s, str>>,
+        Option<similar::InlineChange<'s, str>>,
+    ),
+}
+
+/// Report expected and actual lines in two columns, pairing up replaced lines
+#[cfg(feature = "diff")]
+fn write_side_by_side(
+    writer: &mut dyn std::fmt::Write,
+    ops: Vec<Vec<similar::InlineChange<'_, str>>>,
+    palette: crate::report::Palette,
+    expected_line_offset: usize,
+    actual_line_offset: usize,
+    width: usize,
+) -> Result<(), std::fmt::Error> {
+    // Line number and sign for each side
+    const GUTTER: usize = 7;
+    let column = (width.saturating_sub(2 * GUTTER + 1) / 2).max(10);
+
+    let mut rows = Vec::new();
+    for changes in ops {
+        let mut deleted = Vec::new();
+        let mut inserted = Vec::new();
+        for change in changes {
+            match change.tag() {
+                similar::ChangeTag::Equal => rows.push(Row::Equal(change)),
+                similar::ChangeTag::Delete => deleted.push(change),
+                similar::ChangeTag::Insert => inserted.push(change),
+            }
+        }
+        let paired = deleted.len().max(inserted.len());
+        let mut deleted = deleted.into_iter();
+        let mut inserted = inserted.into_iter();
+        for _ in 0..paired {
+            rows.push(Row::Changed(deleted.next(), inserted.next()));
+        }
+    }
+
+    let is_changed = rows
+        .iter()
+        .map(|row| matches!(row, Row::Changed(..)))
+        .collect::<Vec<_>>();
+    let tombstones = elide(&is_changed, palette.diff_options);
+    let mut elided = false;
+    for (i, row) in rows.into_iter().enumerate() {
+        if tombstones.get(i).copied().unwrap_or(false) {
+            if !elided {
+                let sign = "⋮";
+                write!(writer, "{:>4} {}", " ", palette.hint(sign))?;
+                write!(writer, "{:width$}", "", width = GUTTER - 6 + column + 1)?;
+                writeln!(writer, "{:>4} {}", " ", palette.hint(sign))?;
+            }
+            elided = true;
+            continue;
+        }
+        elided = false;
+
+        let (expected, actual) = match &row {
+            Row::Equal(change) => {
+                let side = (palette.hint, palette.hint, "|");
+                (Some((change, side)), Some((change, side)))
+            }
+            Row::Changed(expected, actual) => (
+                expected
+                    .as_ref()
+                    .map(|change| (change, (palette.expected, palette.error, "-"))),
+                actual
+                    .as_ref()
+                    .map(|change| (change, (palette.actual, palette.info, "+"))),
+            ),
+        };
+        let expected_lines = expected
+            .map(|(change, (em_style, style, _))| {
+                wrap_side(change, em_style, style, palette, column)
+            })
+            .unwrap_or_default();
+        let actual_lines = actual
+            .map(|(change, (em_style, style, _))| {
+                wrap_side(change, em_style, style, palette, column)
+            })
+            .unwrap_or_default();
+        let line_count = expected_lines.len().max(actual_lines.len());
+        for line in 0..line_count {
+            let is_first = line == 0;
+            let actual_line = actual.zip(actual_lines.get(line));
+            match (expected, expected_lines.get(line)) {
+                (Some((change, (_, style, sign))), Some(pieces)) => {
+                    let index = change.old_index().filter(|_| is_first);
+                    write_gutter(
+                        writer,
+                        index,
+                        expected_line_offset,
+                        sign,
+                        style,
+                        is_first,
+                        palette,
+                    )?;
+                    let mut used = 0;
+                    for (style, text) in pieces {
+                        used += text.chars().count();
+                        write!(writer, "{}", Styled::new(text, *style))?;
+                    }
+                    if actual_line.is_some() {
+                        write!(writer, "{:width$}", "", width = column - used)?;
+                    }
+                }
+                _ => {
+                    write!(writer, "{:width$}", "", width = GUTTER + column)?;
+                }
+            }
+            if let Some(((change, (_, style, sign)), pieces)) = actual_line {
+                write!(writer, " ")?;
+                let index = change.new_index().filter(|_| is_first);
+                write_gutter(
+                    writer,
+                    index,
+                    actual_line_offset,
+                    sign,
+                    style,
+                    is_first,
+                    palette,
+                )?;
+                for (style, text) in pieces {
+                    write!(writer, "{}", Styled::new(text, *style))?;
+                }
+            }
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "diff")]
+fn write_gutter(
+    writer: &mut dyn std::fmt::Write,
+    index: Option<usize>,
+    line_offset: usize,
+    sign: &str,
+    style: crate::report::Style,
+    is_first: bool,
+    palette: crate::report::Palette,
+) -> Result<(), std::fmt::Error> {
+    if let Some(index) = index {
+        write!(writer, "{:>4} ", palette.hint(index + 1 + line_offset))?;
+    } else {
+        write!(writer, "{:>4} ", " ")?;
+    }
+    let sign = if is_first { sign } else { " " };
+    write!(writer, "{} ", Styled::new(sign, style))
+}
+
+/// Split a line into pieces no wider than `column`, in total, for each row
+#[cfg(feature = "diff")]
+fn wrap_side(
+    change: &similar::InlineChange<'_, str>,
+    em_style: crate::report::Style,
+    style: crate::report::Style,
+    palette: crate::report::Palette,
+    column: usize,
+) -> Vec<Vec<(crate::report::Style, String)>> {
+    let (em_start, em_end) = match change.tag() {
+        similar::ChangeTag::Delete if palette.is_plain() => ("[-", "-]"),
+        similar::ChangeTag::Insert if palette.is_plain() => ("{+", "+}"),
+        _ => ("", ""),
+    };
+    let mut pieces = Vec::new();
+    for (emphasized, text) in merge_emphasis(change.values()) {
+        let text = text.trim_end_matches(['\n', '\r']).replace('\t', "    ");
+        if text.is_empty() {
+            continue;
+        }
+        if emphasized {
+            pieces.push((em_style, format!("{em_start}{text}{em_end}")));
+        } else {
+            pieces.push((style, text));
+        }
+    }
+    if change.missing_newline() {
+        pieces.push((em_style, "∅".to_owned()));
+    }
+
+    let mut lines = vec![Vec::new()];
+    let mut used = 0;
+    for (style, text) in pieces {
+        let mut current = String::new();
+        for c in text.chars() {
+            if used == column {
+                if !current.is_empty() {
+                    lines
+                        .last_mut()
+                        .unwrap()
+                        .push((style, std::mem::take(&mut current)));
+                }
+                lines.push(Vec::new());
+                used = 0;
+            }
+            current.push(c);
+            used += 1;
+        }
+        if !current.is_empty() {
+            lines.last_mut().unwrap().push((style, current));
+        }
+    }
+    lines
+}
+
+/// Join emphasized runs split by a single unchanged character, like from a character diff
+#[cfg(feature = "diff")]
+fn merge_emphasis(values: &[(bool, &str)]) -> Vec<(bool, String)> {
+    let mut merged: Vec<(bool, String)> = Vec::new();
+    for (i, &(emphasized, value)) in values.iter().enumerate() {
+        let is_between_emphasis = 0 < i
+            && values[i - 1].0
+            && values.get(i + 1).is_some_and(|(next, _)| *next)
+            && value.chars().count() == 1;
+        let emphasized = emphasized || is_between_emphasis;
+        match merged.last_mut() {
+            Some((last_emphasized, last)) if *last_emphasized == emphasized => {
+                last.push_str(value);
+            }
+            _ => merged.push((emphasized, value.to_owned())),
+        }
+    }
+    merged
+}
+
+/// Whether emphasis points out what changed within lines, rather than everything
+#[cfg(feature = "diff")]
+fn is_partially_emphasized(changes: &[similar::InlineChange<'_, str>]) -> bool {
+    let mut emphasized = false;
+    let mut unemphasized = false;
+    for &(is_emphasized, value) in changes.iter().flat_map(|change| change.values()) {
+        if value.trim().is_empty() {
+            continue;
+        }
+        if is_emphasized {
+            emphasized = true;
+        } else {
+            unemphasized = true;
+        }
+    }
+    emphasized && unemphasized
+}
+
+#[cfg(feature = "structured-data")]
+fn structured_value(data: &crate::Data) -> Option<&serde_json::Value> {
+    match &data.inner.value {
+        #[cfg(feature = "json")]
+        crate::data::DataValue::Json(value) | crate::data::DataValue::JsonLines(value) => {
+            Some(value)
+        }
+        #[cfg(feature = "yaml")]
+        crate::data::DataValue::Yaml(value) => Some(value),
+        #[cfg(feature = "toml")]
+        crate::data::DataValue::Toml(value, _) => Some(value),
+        _ => None,
+    }
+}
+
+#[cfg(any(feature = "diff", feature = "structured-data"))]
+pub(super) fn write_header(
+    writer: &mut dyn std::fmt::Write,
+    expected_name: Option<&dyn std::fmt::Display>,
+    actual_name: Option<&dyn std::fmt::Display>,
+    palette: crate::report::Palette,
+) -> Result<(), std::fmt::Error> {
+    writeln!(writer)?;
+    if let Some(expected_name) = expected_name {
+        writeln!(
+            writer,
+            "{}",
+            palette.error(format_args!("{:->4} expected: {}", "", expected_name))
+        )?;
+    } else {
+        writeln!(writer, "{}", palette.error(format_args!("--- Expected")))?;
+    }
+    if let Some(actual_name) = actual_name {
+        writeln!(
+            writer,
+            "{}",
+            palette.info(format_args!("{:+>4} actual:   {}", "", actual_name))
+        )?;
+    } else {
+        writeln!(writer, "{}", palette.info(format_args!("+++ Actual")))?;
+    }
+    Ok(())
+}
+
+/// Report differences by row and column, rather than by line
+///
+/// Cells can contain newlines, so line numbers would not line up with records.
+#[cfg(all(feature = "diff", feature = "csv"))]
+fn write_records_diff(
+    writer: &mut dyn std::fmt::Write,
+    expected: &[Vec<String>],
+    actual: &[Vec<String>],
+    expected_name: Option<&dyn std::fmt::Display>,
+    actual_name: Option<&dyn std::fmt::Display>,
+    palette: crate::report::Palette,
+) -> Result<(), std::fmt::Error> {
+    write_header(writer, expected_name, actual_name, palette)?;
+
+    let deadline = std::time::Instant::now() + palette.diff_options.timeout;
+    let ops = similar::capture_diff_slices_deadline(
+        similar::Algorithm::Patience,
+        expected,
+        actual,
+        Some(deadline),
+    );
+    let mut rows = Vec::new();
+    for op in ops {
+        let (tag, expected_range, actual_range) = op.as_tag_tuple();
+        match tag {
+            similar::DiffTag::Equal => {
+                rows.extend(expected_range.zip(actual_range).map(
+                    |(expected_index, actual_index)| RecordRow::Equal(expected_index, actual_index),
+                ));
+            }
+            similar::DiffTag::Delete => {
+                rows.extend(expected_range.map(RecordRow::Removed));
+            }
+            similar::DiffTag::Insert => {
+                rows.extend(actual_range.map(RecordRow::Added));
+            }
+            similar::DiffTag::Replace => {
+                let paired = expected_range.len().min(actual_range.len());
+                for (expected_index, actual_index) in
+                    expected_range.clone().zip(actual_range.clone())
+                {
+                    if expected[expected_index].len() == actual[actual_index].len() {
+                        rows.push(RecordRow::Changed(expected_index, actual_index));
+                    } else {
+                        rows.push(RecordRow::Removed(expected_index));
+                        rows.push(RecordRow::Added(actual_index));
+                    }
+                }
+                rows.extend(expected_range.skip(paired).map(RecordRow::Removed));
+                rows.extend(actual_range.skip(paired).map(RecordRow::Added));
+            }
+        }
+    }
+
+    let is_changed = rows
+        .iter()
+        .map(|row| !matches!(row, RecordRow::Equal(..)))
+        .collect::<Vec<_>>();
+    let tombstones = elide(&is_changed, palette.diff_options);
+
+    let mut elided = false;
+    for (i, row) in rows.into_iter().enumerate() {
+        if tombstones.get(i).copied().unwrap_or(false) {
+            if !elided {
+                let sign = "⋮";
+
+                write!(writer, "{:>4} ", " ",)?;
+                write!(writer, "{:>4} ", " ",)?;
+                writeln!(writer, "{}", palette.hint(sign))?;
+            }
+            elided = true;
+            continue;
+        }
+        elided = false;
+        match row {
+            RecordRow::Equal(expected_index, actual_index) => {
+                write_record(
+                    writer,
+                    Some(expected_index),
+                    Some(actual_index),
+                    &expected[expected_index],
+                    palette,
+                )?;
+            }
+            RecordRow::Removed(expected_index) => {
+                write_record(
+                    writer,
+                    Some(expected_index),
+                    None,
+                    &expected[expected_index],
+                    palette,
+                )?;
+            }
+            RecordRow::Added(actual_index) => {
+                write_record(
+                    writer,
+                    None,
+                    Some(actual_index),
+                    &actual[actual_index],
+                    palette,
+                )?;
+            }
+            RecordRow::Changed(expected_index, actual_index) => {
+                for (column, (expected_cell, actual_cell)) in expected[expected_index]
+                    .iter()
+                    .zip(&actual[actual_index])
+                    .enumerate()
+                {
+                    if expected_cell != actual_cell {
+                        write!(writer, "{:>4} ", palette.hint(expected_index + 1))?;
+                        write!(writer, "{:>4} ", palette.hint(actual_index + 1))?;
+                        writeln!(
+                            writer,
+                            "{} column {}: {} → {}",
+                            palette.hint("~"),
+                            column + 1,
+                            palette.expected(format_args!("{expected_cell:?}")),
+                            palette.actual(format_args!("{actual_cell:?}")),
+                        )?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A record of [`write_records_diff`], by its index in expected and/or actual
+#[cfg(all(feature = "diff", feature = "csv"))]
+enum RecordRow {
+    Equal(usize, usize),
+    Removed(usize),
+    Added(usize),
+    /// Same number of cells, reported by column
+    Changed(usize, usize),
+}
+
+#[cfg(all(feature = "diff", feature = "csv"))]
+fn write_record(
+    writer: &mut dyn std::fmt::Write,
+    expected_index: Option<usize>,
+    actual_index: Option<usize>,
+    record: &[String],
+    palette: crate::report::Palette,
+) -> Result<(), std::fmt::Error> {
+    let (sign, style) = match (expected_index, actual_index) {
+        (Some(_), Some(_)) => ("|", palette.hint),
+        (Some(_), None) => ("-", palette.error),
+        (None, _) => ("+", palette.info),
+    };
+    for index in [expected_index, actual_index] {
+        if let Some(index) = index {
+            write!(writer, "{:>4} ", palette.hint(index + 1))?;
+        } else {
+            write!(writer, "{:>4} ", " ")?;
+        }
+    }
+    write!(writer, "{} ", Styled::new(sign, style))?;
+    for (column, cell) in record.iter().enumerate() {
+        if column != 0 {
+            write!(writer, "{}", Styled::new(", ", style))?;
+        }
+        write!(writer, "{}", Styled::new(format_args!("{cell:?}"), style))?;
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
 #[cfg(feature = "diff")]
 #[allow(clippy::too_many_arguments)]
 fn write_change(
@@ -243,9 +957,32 @@ fn write_change(
         write!(writer, "{:>4} ", " ",)?;
     }
     write!(writer, "{} ", Styled::new(sign, style))?;
-    for &(emphasized, change) in change.values() {
-        let cur_style = if emphasized { em_style } else { style };
-        write!(writer, "{}", Styled::new(change, cur_style))?;
+    // Without colors, mark emphasis like `git diff --word-diff`
+    let (em_start, em_end) = match change.tag() {
+        similar::ChangeTag::Delete if palette.is_plain() => ("[-", "-]"),
+        similar::ChangeTag::Insert if palette.is_plain() => ("{+", "+}"),
+        _ => ("", ""),
+    };
+    for (emphasized, change) in merge_emphasis(change.values()) {
+        let change = change.as_str();
+        if emphasized {
+            let (change, newline) = change
+                .strip_suffix('\n')
+                .map(|change| (change, "\n"))
+                .unwrap_or((change, ""));
+            if !change.is_empty() {
+                write!(
+                    writer,
+                    "{}",
+                    Styled::new(format_args!("{em_start}{change}{em_end}"), em_style)
+                )?;
+            }
+            if !newline.is_empty() {
+                write!(writer, "{}", Styled::new(newline, em_style))?;
+            }
+        } else {
+            write!(writer, "{}", Styled::new(change, style))?;
+        }
     }
     if change.missing_newline() {
         writeln!(writer, "{}", Styled::new("∅", em_style))?;
@@ -465,6 +1202,264 @@ mod test {
         assert_eq!(expected_diff, actual_diff);
     }
 
+    #[cfg(feature = "diff")]
+    #[test]
+    fn diff_ne_inline_words() {
+        let expected = "2024-01-01 INFO request served in 12ms from cache\n";
+        let expected_name = "A";
+        let actual = "2024-01-01 WARN request served in 340ms from origin\n";
+        let actual_name = "B";
+        let palette = crate::report::Palette::plain();
+
+        let mut actual_diff = String::new();
+        write_diff_inner(
+            &mut actual_diff,
+            expected,
+            actual,
+            Some(&expected_name),
+            Some(&actual_name),
+            palette,
+            0,
+            0,
+        )
+        .unwrap();
+        let expected_diff = "
+---- expected: A
+++++ actual:   B
+   1      - 2024-01-01 [-INFO-] request served in [-12ms-] from [-cache-]
+        1 + 2024-01-01 {+WARN+} request served in {+340ms+} from {+origin+}
+";
+
+        assert_eq!(expected_diff, actual_diff);
+    }
+
+    #[cfg(feature = "diff")]
+    #[test]
+    fn diff_ne_inline_chars() {
+        let expected = r#"{"name":"snapbox","version":"1.0.0","features":["diff","color"]}"#;
+        let expected_name = "A";
+        let actual = r#"{"name":"snapbox","version":"1.1.0","features":["diff","json"]}"#;
+        let actual_name = "B";
+        let palette = crate::report::Palette::plain();
+
+        let mut actual_diff = String::new();
+        write_diff_inner(
+            &mut actual_diff,
+            expected,
+            actual,
+            Some(&expected_name),
+            Some(&actual_name),
+            palette,
+            0,
+            0,
+        )
+        .unwrap();
+        let expected_diff = r#"
+---- expected: A
+++++ actual:   B
+   1      - {"name":"snapbox","version":"1.[-0-].0","features":["diff","[-color-]"]}∅
+        1 + {"name":"snapbox","version":"1.{+1+}.0","features":["diff","{+json+}"]}∅
+"#;
+
+        assert_eq!(expected_diff, actual_diff);
+    }
+
+    #[cfg(feature = "diff")]
+    #[test]
+    fn diff_ne_inline_chars_unrelated() {
+        let expected = r#"["parse","lex","emit"]"#;
+        let expected_name = "A";
+        let actual = r#"["print","lint","exit"]"#;
+        let actual_name = "B";
+        let palette = crate::report::Palette::plain();
+
+        let mut actual_diff = String::new();
+        write_diff_inner(
+            &mut actual_diff,
+            expected,
+            actual,
+            Some(&expected_name),
+            Some(&actual_name),
+            palette,
+            0,
+            0,
+        )
+        .unwrap();
+        let expected_diff = r#"
+---- expected: A
+++++ actual:   B
+   1      - ["parse","lex","emit"]∅
+        1 + ["print","lint","exit"]∅
+"#;
+
+        assert_eq!(expected_diff, actual_diff);
+    }
+
+    #[cfg(feature = "diff")]
+    #[test]
+    fn diff_ne_side_by_side() {
+        let expected = "Hello\nWorld\nthis line is long enough to wrap around\n!\nremoved";
+        let expected_name = "A";
+        let actual = "Hello\nMoon\nthis line is long enough to wrap around\n?\nmore\n";
+        let actual_name = "B";
+        let palette = crate::report::Palette::plain().diff_layout(DiffLayout::SideBySide);
+
+        let mut actual_diff = String::new();
+        write_text_diff(
+            &mut actual_diff,
+            expected,
+            actual,
+            Some(&expected_name),
+            Some(&actual_name),
+            palette,
+            0,
+            0,
+            55,
+        )
+        .unwrap();
+        let expected_diff = "
+---- expected: A
+++++ actual:   B
+   1 | Hello                   1 | Hello
+   2 - World                   2 + Moon
+   3 | this line is long en    3 | this line is long en
+       ough to wrap around         ough to wrap around
+   4 - !                       4 + ?
+   5 - removed∅                5 + more
+";
+
+        assert_eq!(expected_diff, actual_diff);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn diff_ne_structural() {
+        let expected = crate::Data::json(serde_json::json!({"name": "a", "id": "{...}"}));
+        let expected_name = "A";
+        let actual = crate::Data::json(serde_json::json!({"name": "b", "id": 5, "new": null}));
+        let actual_name = "B";
+        let palette = crate::report::Palette::plain().diff_layout(DiffLayout::Structural);
+
+        let mut actual_diff = String::new();
+        write_diff(
+            &mut actual_diff,
+            &expected,
+            &actual,
+            Some(&expected_name),
+            Some(&actual_name),
+            palette,
+        )
+        .unwrap();
+        let expected_diff = r#"
+---- expected: A
+++++ actual:   B
+~ /name: "a" → "b"
++ /new: null
+"#;
+
+        assert_eq!(expected_diff, actual_diff);
+    }
+
+    #[cfg(feature = "diff")]
+    #[test]
+    fn diff_ne_binary() {
+        let expected = crate::Data::binary(b"\x00\x01Hello, World!\n".to_vec());
+        let expected_name = "A";
+        let actual = crate::Data::binary(b"\x00\x01Hello, World?\n".to_vec());
+        let actual_name = "B";
+        let palette = crate::report::Palette::plain();
+
+        let mut actual_diff = String::new();
+        write_diff(
+            &mut actual_diff,
+            &expected,
+            &actual,
+            Some(&expected_name),
+            Some(&actual_name),
+            palette,
+        )
+        .unwrap();
+        let expected_diff = "
+---- expected: A
+++++ actual:   B
+   1      - 00000000  00 01 48 65 6c 6c 6f 2c  20 57 6f 72 6c 64 [-21-] 0a  |..Hello, [-World!.|-]
+        1 + 00000000  00 01 48 65 6c 6c 6f 2c  20 57 6f 72 6c 64 {+3f+} 0a  |..Hello, {+World?.|+}
+";
+
+        assert_eq!(expected_diff, actual_diff);
+    }
+
+    #[cfg(feature = "diff")]
+    #[cfg(feature = "csv")]
+    #[test]
+    fn diff_ne_records() {
+        use crate::IntoData as _;
+
+        let expected = "name,notes\nfoo,\"one\ntwo\"\nbar,1\nbaz,2\n".is_csv();
+        let expected_name = "A";
+        let actual = "name,notes\nfoo,\"one\nthree\"\nbar,1\nqux,2,extra\n".is_csv();
+        let actual_name = "B";
+        let palette = crate::report::Palette::plain();
+
+        let mut actual_diff = String::new();
+        write_diff(
+            &mut actual_diff,
+            &expected,
+            &actual,
+            Some(&expected_name),
+            Some(&actual_name),
+            palette,
+        )
+        .unwrap();
+        let expected_diff = r#"
+---- expected: A
+++++ actual:   B
+   1    1 | "name", "notes"
+   2    2 ~ column 2: "one\ntwo" → "one\nthree"
+   3    3 | "bar", "1"
+   4      - "baz", "2"
+        4 + "qux", "2", "extra"
+"#;
+
+        assert_eq!(expected_diff, actual_diff);
+    }
+
+    #[cfg(feature = "diff")]
+    #[cfg(feature = "csv")]
+    #[test]
+    fn diff_ne_records_elided() {
+        use crate::IntoData as _;
+
+        let expected = (1..=10).map(|i| format!("{i},x\n")).collect::<String>();
+        let actual = expected.replace("5,x", "5,y");
+        let palette = crate::report::Palette::plain()
+            .diff_options(DiffOptions::new().context(1).min_elide(0));
+
+        let mut actual_diff = String::new();
+        write_diff(
+            &mut actual_diff,
+            &expected.is_csv(),
+            &actual.is_csv(),
+            None,
+            None,
+            palette,
+        )
+        .unwrap();
+        let expected_diff = r#"
+--- Expected
++++ Actual
+   1    1 | "1", "x"
+          ⋮
+   4    4 | "4", "x"
+   5    5 ~ column 2: "x" → "y"
+   6    6 | "6", "x"
+          ⋮
+  10   10 | "10", "x"
+"#;
+
+        assert_eq!(expected_diff, actual_diff);
+    }
+
     #[cfg(feature = "diff")]
     #[cfg(feature = "term-svg")]
     #[test]
@@ -497,11 +1492,47 @@ Hello World
 ---- expected: A
 ++++ actual:   B
    2    2 | <text>
-   3      - Hello Moon
-        3 + Hello World
+   3      - Hello [-Moon-]
+        3 + Hello {+World+}
    4    4 | </text>
 ";
 
         assert_eq!(expected_diff, actual_diff);
     }
+
+    #[test]
+    fn diff_options_env() {
+        let cases = [
+            ("", Some(DiffOptions::new())),
+            (
+                "context=1, elide=never,timeout=5000",
+                Some(
+                    DiffOptions::new()
+                        .context(1)
+                        .never_elide()
+                        .timeout(std::time::Duration::from_secs(5)),
+                ),
+            ),
+            ("elide=3", Some(DiffOptions::new().min_elide(3))),
+            ("context", None),
+            ("context=all", None),
+            ("unknown=1", None),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(DiffOptions::with_env_value(value), expected, "{value:?}");
+        }
+    }
+
+    #[cfg(feature = "diff")]
+    #[test]
+    fn elide_with_options() {
+        let is_changed = [false, false, false, true, false, false, false];
+        let options = DiffOptions::new().context(1).min_elide(3);
+        assert_eq!(
+            elide(&is_changed, options),
+            [false, true, false, false, false, true, false]
+        );
+        assert_eq!(elide(&is_changed, options.min_elide(7)), [false; 0]);
+        assert_eq!(elide(&is_changed, options.never_elide()), [false; 0]);
+    }
 }
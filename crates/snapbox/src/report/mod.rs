@@ -2,8 +2,25 @@
 
 mod color;
 mod diff;
+#[cfg(feature = "diff")]
+mod patch;
+mod record;
+#[cfg(feature = "structured-data")]
+mod structural;
 
 pub use color::Palette;
 pub(crate) use color::Style;
 pub use color::Styled;
+pub use diff::DEFAULT_DIFF_ENV;
+pub use diff::DEFAULT_DIFF_OPTIONS_ENV;
+pub use diff::DiffLayout;
+pub use diff::DiffOptions;
 pub use diff::write_diff;
+#[cfg(feature = "diff")]
+pub use patch::DEFAULT_PATCH_DIR_ENV;
+#[cfg(feature = "diff")]
+pub use patch::PatchDir;
+pub use record::DEFAULT_REPORT_ENV;
+pub use record::Report;
+pub use record::ReportEntry;
+pub use record::ReportFormat;
@@ -0,0 +1,234 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Default environment variable for selecting a [`PatchDir`]
+pub const DEFAULT_PATCH_DIR_ENV: &str = "SNAPSHOTS_PATCH_DIR";
+
+/// Directory to write a `.patch` file to for each snapshot file that does not match
+///
+/// Each patch is a unified diff that updates the snapshot like
+/// [`Action::Overwrite`][crate::assert::Action::Overwrite] would, with paths relative to the git
+/// repository, so it can be applied with `git apply`.  The patch is removed once the snapshot
+/// matches.
+///
+/// Patches mirror the snapshot's path within the repository, so
+/// `tests/snapshots/hello.txt` is patched by `<dir>/tests/snapshots/hello.txt.patch`.
+///
+/// [`str!`][crate::str!] snapshots are not written as patches.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use snapbox::Assert;
+/// # use snapbox::report::PatchDir;
+/// Assert::new()
+///     .patch_dir(PatchDir::new("target/snapshot-patches"))
+///     .eq("something", "something");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchDir {
+    path: PathBuf,
+}
+
+impl PatchDir {
+    /// Write patches under `path`, creating it as needed
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Read the directory from an environment variable
+    pub fn with_env_var(var: impl AsRef<std::ffi::OsStr>) -> Option<Self> {
+        let value = std::env::var_os(var)?;
+        if value.is_empty() {
+            return None;
+        }
+        Some(Self::new(value))
+    }
+
+    /// The directory patches are written under
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write a patch updating the snapshot file at `target` to `actual`
+    ///
+    /// Returns where the patch was written, unless the file already matches.
+    pub fn write(
+        &self,
+        target: &Path,
+        actual: &crate::Data,
+    ) -> crate::assert::Result<Option<PathBuf>> {
+        let old = match std::fs::read(target) {
+            Ok(old) => Some(old),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(format!("Failed to read {}: {}", target.display(), err).into()),
+        };
+        let new = actual.to_file_bytes(target)?;
+        if old.as_deref() == Some(new.as_slice()) {
+            self.clear(target)?;
+            return Ok(None);
+        }
+
+        let binary = || format!("Cannot write a patch for binary file {}", target.display());
+        let old = old
+            .as_deref()
+            .map(std::str::from_utf8)
+            .transpose()
+            .map_err(|_| binary())?;
+        let new = std::str::from_utf8(&new).map_err(|_| binary())?;
+        let name = repo_relpath(target);
+        let patch = render_patch(&name, old, new);
+
+        let patch_path = self.patch_path(&name);
+        let patch_parent = patch_path.parent().unwrap_or(&self.path);
+        std::fs::create_dir_all(patch_parent)
+            .map_err(|e| format!("Failed to create {}: {}", patch_parent.display(), e))?;
+        std::fs::write(&patch_path, patch)
+            .map_err(|e| format!("Failed to write {}: {}", patch_path.display(), e))?;
+        Ok(Some(patch_path))
+    }
+
+    /// Remove the patch for the snapshot file at `target`, if any
+    pub fn clear(&self, target: &Path) -> crate::assert::Result<()> {
+        let patch_path = self.patch_path(&repo_relpath(target));
+        match std::fs::remove_file(&patch_path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("Failed to remove {}: {}", patch_path.display(), err).into()),
+        }
+    }
+
+    /// Mirror the snapshot's path so distinct snapshots can't share a patch
+    fn patch_path(&self, name: &str) -> PathBuf {
+        self.path.join(format!("{name}.patch"))
+    }
+}
+
+/// A missing `old` file is created by the patch
+fn render_patch(name: &str, old: Option<&str>, new: &str) -> String {
+    let old_header = if old.is_some() {
+        format!("a/{name}")
+    } else {
+        "/dev/null".to_owned()
+    };
+    similar::TextDiff::from_lines(old.unwrap_or_default(), new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&old_header, &format!("b/{name}"))
+        .to_string()
+}
+
+/// `target` relative to the git repository it is in, with `/` separators
+fn repo_relpath(target: &Path) -> String {
+    let target = std::path::absolute(target).unwrap_or_else(|_| target.to_owned());
+    let root = target
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join(".git").exists());
+    let relpath = root
+        .and_then(|root| target.strip_prefix(root).ok())
+        .unwrap_or(&target);
+    relpath
+        .components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let patch = render_patch(
+            "tests/snapshots/hello.txt",
+            Some("Hello\nWorld\n"),
+            "Hello\nMoon\n",
+        );
+        assert_eq!(
+            patch,
+            "\
+--- a/tests/snapshots/hello.txt
++++ b/tests/snapshots/hello.txt
+@@ -1,2 +1,2 @@
+ Hello
+-World
++Moon
+"
+        );
+
+        let patch = render_patch("new.txt", None, "Hello");
+        assert_eq!(
+            patch,
+            "\
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1 @@
++Hello
+\\ No newline at end of file
+"
+        );
+    }
+
+    #[cfg(feature = "dir")]
+    #[test]
+    fn write_and_clear() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join(".git")).unwrap();
+        let target = root.path().join("tests/snapshots/hello.txt");
+        std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+        std::fs::write(&target, "Hello\nWorld\n").unwrap();
+        let patches = PatchDir::new(root.path().join("patches"));
+
+        let patch_path = patches
+            .write(&target, &crate::Data::text("Hello\nMoon\n"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            patch_path,
+            root.path().join("patches/tests/snapshots/hello.txt.patch")
+        );
+        let patch = std::fs::read_to_string(&patch_path).unwrap();
+        assert!(
+            patch.starts_with("--- a/tests/snapshots/hello.txt\n"),
+            "{patch}"
+        );
+
+        let written = patches
+            .write(&target, &crate::Data::text("Hello\nWorld\n"))
+            .unwrap();
+        assert_eq!(written, None);
+        assert!(!patch_path.exists());
+    }
+
+    #[cfg(feature = "dir")]
+    #[test]
+    fn nested_names_stay_distinct() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join(".git")).unwrap();
+        let patches = PatchDir::new(root.path().join("patches"));
+
+        let nested = root.path().join("tests/snapshots/a/b.txt");
+        let flat = root.path().join("tests/snapshots/a__b.txt");
+        let nested_patch = patches
+            .write(&nested, &crate::Data::text("nested\n"))
+            .unwrap()
+            .unwrap();
+        let flat_patch = patches
+            .write(&flat, &crate::Data::text("flat\n"))
+            .unwrap()
+            .unwrap();
+        assert_ne!(nested_patch, flat_patch);
+        let patch = std::fs::read_to_string(&nested_patch).unwrap();
+        assert!(patch.contains("+++ b/tests/snapshots/a/b.txt\n"), "{patch}");
+        let patch = std::fs::read_to_string(&flat_patch).unwrap();
+        assert!(
+            patch.contains("+++ b/tests/snapshots/a__b.txt\n"),
+            "{patch}"
+        );
+    }
+}
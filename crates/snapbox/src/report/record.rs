@@ -0,0 +1,551 @@
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::utils::encode_json_str;
+
+/// Default environment variable for selecting a [`Report`]
+pub const DEFAULT_REPORT_ENV: &str = "SNAPSHOTS_REPORT";
+
+/// Machine-readable record of each snapshot comparison
+///
+/// Unlike the failure messages, this is meant for tools like CI dashboards.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use snapbox::Assert;
+/// # use snapbox::report::Report;
+/// Assert::new()
+///     .report(Report::new("target/snapshots.xml"))
+///     .eq("something", "something");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    path: PathBuf,
+    format: ReportFormat,
+}
+
+impl Report {
+    /// Record comparisons to `path`, in a format inferred from its extension
+    ///
+    /// `.xml` files are [`JUnit` XML][ReportFormat::JUnit] and anything else is
+    /// [JSON Lines][ReportFormat::JsonLines].
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let format = if path.extension().is_some_and(|ext| ext == "xml") {
+            ReportFormat::JUnit
+        } else {
+            ReportFormat::JsonLines
+        };
+        Self { path, format }
+    }
+
+    /// Read the report path from an environment variable, see [`Report::new`]
+    pub fn with_env_var(var: impl AsRef<std::ffi::OsStr>) -> Option<Self> {
+        let value = std::env::var_os(var)?;
+        if value.is_empty() {
+            return None;
+        }
+        Some(Self::new(value))
+    }
+
+    /// Override the inferred format
+    pub fn format(mut self, format: ReportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Where comparisons are recorded
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Add `entry` to the report
+    ///
+    /// JSON Lines are appended to the file.  `JUnit` XML is rewritten with each entry, keeping one
+    /// `<testsuite>` per test binary, named after the binary without cargo's hash.  Entries are
+    /// merged into their suite by name, so test processes, like with `cargo nextest`, each add
+    /// theirs and re-running a comparison replaces its entry.  Entries of removed tests stay until
+    /// the file is removed.
+    ///
+    /// Rewriting `JUnit` XML is serialized across processes through a `.lock` file next to it.
+    pub fn write(&self, entry: &ReportEntry) -> crate::assert::Result<()> {
+        let _guard = REPORT_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let result = match self.format {
+            ReportFormat::JsonLines => self.append_json(entry),
+            ReportFormat::JUnit => self.write_junit(entry),
+        };
+        result.map_err(|e| format!("Failed to write {}: {}", self.path.display(), e).into())
+    }
+
+    fn append_json(&self, entry: &ReportEntry) -> std::io::Result<()> {
+        create_parent_dir(&self.path)?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(format!("{}\n", entry.to_json()).as_bytes())
+    }
+
+    fn write_junit(&self, entry: &ReportEntry) -> std::io::Result<()> {
+        create_parent_dir(&self.path)?;
+        let _lock = ReportLock::acquire(&self.path)?;
+        let existing = match std::fs::read_to_string(&self.path) {
+            Ok(existing) => existing,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        std::fs::write(&self.path, merge_junit(&existing, &suite_name(), entry))
+    }
+}
+
+/// Exclusive access to a report across processes, released on drop
+struct ReportLock {
+    path: PathBuf,
+}
+
+impl ReportLock {
+    /// A lock this old was left by a process that died while holding it
+    const STALE: Duration = Duration::from_secs(30);
+
+    fn acquire(report: &Path) -> std::io::Result<Self> {
+        let mut path = report.as_os_str().to_owned();
+        path.push(".lock");
+        let path = PathBuf::from(path);
+        loop {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(_) => return Ok(Self { path }),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let is_stale = std::fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| Self::STALE < age);
+                    if is_stale {
+                        let _ = std::fs::remove_file(&path);
+                    } else {
+                        std::thread::sleep(Duration::from_millis(5));
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Drop for ReportLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Supported [`Report`] formats
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReportFormat {
+    /// One JSON object per line, per comparison
+    ///
+    /// Each object has the fields `name`, `expected`, `status` (`"pass"` or `"fail"`), `action`,
+    /// `duration` (seconds) and `diff`, with `null` for missing values.
+    JsonLines,
+    /// A `<testcase>` per comparison, grouped in a `<testsuite>` per test binary
+    JUnit,
+}
+
+/// The result of one snapshot comparison, see [`Report`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReportEntry {
+    name: String,
+    passed: bool,
+    expected: Option<String>,
+    action: Option<String>,
+    duration: Option<Duration>,
+    diff: Option<String>,
+}
+
+impl ReportEntry {
+    /// Record whether the comparison named `name` passed
+    ///
+    /// `name` identifies the entry in the report, so it should be unique per comparison.
+    pub fn new(name: impl Into<String>, passed: bool) -> Self {
+        Self {
+            name: name.into(),
+            passed,
+            expected: None,
+            action: None,
+            duration: None,
+            diff: None,
+        }
+    }
+
+    /// Where the expected value came from, like a snapshot file
+    pub fn expected(mut self, source: impl std::fmt::Display) -> Self {
+        self.expected = Some(source.to_string());
+        self
+    }
+
+    /// What was done about a mismatch, like `overwrite`
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    /// How long the comparison took
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Describe the mismatch, without styling
+    pub fn diff(mut self, diff: impl Into<String>) -> Self {
+        self.diff = Some(diff.into());
+        self
+    }
+
+    fn status(&self) -> &'static str {
+        if self.passed { "pass" } else { "fail" }
+    }
+
+    fn to_json(&self) -> String {
+        fn encode_opt(buf: &mut String, value: Option<&str>) {
+            match value {
+                Some(value) => encode_json_str(buf, value),
+                None => buf.push_str("null"),
+            }
+        }
+
+        let mut buf = String::new();
+        buf.push_str("{\"name\":");
+        encode_json_str(&mut buf, &self.name);
+        buf.push_str(",\"expected\":");
+        encode_opt(&mut buf, self.expected.as_deref());
+        buf.push_str(",\"status\":");
+        encode_json_str(&mut buf, self.status());
+        buf.push_str(",\"action\":");
+        encode_opt(&mut buf, self.action.as_deref());
+        buf.push_str(",\"duration\":");
+        match self.duration {
+            Some(duration) => buf.push_str(&duration.as_secs_f64().to_string()),
+            None => buf.push_str("null"),
+        }
+        buf.push_str(",\"diff\":");
+        encode_opt(&mut buf, self.diff.as_deref());
+        buf.push('}');
+        buf
+    }
+
+    fn to_junit(&self, suite: &str) -> String {
+        let mut buf = format!(
+            "<testcase name=\"{}\" classname=\"{}\"",
+            escape_xml(&self.name),
+            escape_xml(suite)
+        );
+        if let Some(duration) = self.duration {
+            buf.push_str(&format!(" time=\"{}\"", duration.as_secs_f64()));
+        }
+        buf.push('>');
+        let properties = [("expected", &self.expected), ("action", &self.action)];
+        if properties.iter().any(|(_, value)| value.is_some()) {
+            buf.push_str("<properties>");
+            for (name, value) in properties {
+                if let Some(value) = value {
+                    buf.push_str(&format!(
+                        "<property name=\"{name}\" value=\"{}\"/>",
+                        escape_xml(value)
+                    ));
+                }
+            }
+            buf.push_str("</properties>");
+        }
+        if !self.passed {
+            buf.push_str("<failure message=\"snapshot mismatch\">");
+            buf.push_str(&escape_xml(self.diff.as_deref().unwrap_or_default()));
+            buf.push_str("</failure>");
+        }
+        buf.push_str("</testcase>");
+        buf
+    }
+}
+
+static REPORT_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+const JUNIT_HEADER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n";
+const JUNIT_FOOTER: &str = "</testsuites>\n";
+const SUITE_START: &str = "<testsuite ";
+const SUITE_END: &str = "</testsuite>";
+const CASE_START: &str = "<testcase ";
+
+/// Identify the test binary
+fn suite_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            exe.file_stem()
+                .map(|stem| strip_cargo_hash(&stem.to_string_lossy()).to_owned())
+        })
+        .unwrap_or_else(|| "snapbox".to_owned())
+}
+
+/// Cargo names test binaries `{target}-{hash}`, with a hash that changes between builds
+fn strip_cargo_hash(stem: &str) -> &str {
+    match stem.rsplit_once('-') {
+        Some((name, hash))
+            if !name.is_empty()
+                && hash.len() == 16
+                && hash.bytes().all(|b| b.is_ascii_hexdigit()) =>
+        {
+            name
+        }
+        _ => stem,
+    }
+}
+
+/// `cases` are from [`ReportEntry::to_junit`]
+fn render_junit_suite(name: &str, cases: &[String]) -> String {
+    let failures = cases
+        .iter()
+        .filter(|case| case.contains("<failure "))
+        .count();
+    let time = cases
+        .iter()
+        .filter_map(|case| {
+            let start = case.split_once(" time=\"")?.1;
+            let (time, _) = start.split_once('"')?;
+            time.parse::<f64>().ok()
+        })
+        .fold(0.0, |total, time| total + time);
+    let mut buf = format!(
+        "{SUITE_START}name=\"{}\" tests=\"{}\" failures=\"{failures}\" time=\"{time}\">\n",
+        escape_xml(name),
+        cases.len(),
+    );
+    for case in cases {
+        buf.push_str(case);
+        buf.push('\n');
+    }
+    buf.push_str(SUITE_END);
+    buf.push('\n');
+    buf
+}
+
+/// Add `entry` to the `<testsuite>` named `suite`, replacing its `<testcase>` of the same name
+fn merge_junit(existing: &str, suite: &str, entry: &ReportEntry) -> String {
+    let start = format!("{SUITE_START}name=\"{}\" ", escape_xml(suite));
+    let case_start = format!("{CASE_START}name=\"{}\" ", escape_xml(&entry.name));
+
+    let mut merged = JUNIT_HEADER.to_owned();
+    // `<testcase>`s can span lines, but markup within them is escaped
+    let mut cases = Vec::<String>::new();
+    let mut in_suite = false;
+    for line in existing.lines() {
+        if line.starts_with(SUITE_START) {
+            in_suite = line.starts_with(&start);
+            if in_suite {
+                continue;
+            }
+        }
+        if in_suite {
+            if line == SUITE_END {
+                in_suite = false;
+            } else if line.starts_with(CASE_START) {
+                cases.push(line.to_owned());
+            } else if let Some(case) = cases.last_mut() {
+                case.push('\n');
+                case.push_str(line);
+            }
+            continue;
+        }
+        let is_envelope = line.starts_with("<?xml") || line.contains("testsuites>");
+        if !is_envelope {
+            merged.push_str(line);
+            merged.push('\n');
+        }
+    }
+    let case = entry.to_junit(suite);
+    match cases.iter_mut().find(|case| case.starts_with(&case_start)) {
+        Some(existing) => *existing = case,
+        None => cases.push(case),
+    }
+    merged.push_str(&render_junit_suite(suite, &cases));
+    merged.push_str(JUNIT_FOOTER);
+    merged
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\n' | '\t' => escaped.push(c),
+            '\r' => escaped.push_str("&#13;"),
+            // Not allowed in XML 1.0
+            c if c < ' ' => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn create_parent_dir(path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn infer_format() {
+        assert_eq!(Report::new("report.xml").format, ReportFormat::JUnit);
+        assert_eq!(Report::new("report.jsonl").format, ReportFormat::JsonLines);
+        assert_eq!(
+            Report::new("report.xml")
+                .format(ReportFormat::JsonLines)
+                .format,
+            ReportFormat::JsonLines
+        );
+    }
+
+    #[test]
+    fn entry_json() {
+        let passed = ReportEntry::new("module::passed", true);
+        assert_eq!(
+            passed.to_json(),
+            r#"{"name":"module::passed","expected":null,"status":"pass","action":null,"duration":null,"diff":null}"#
+        );
+
+        let failed = ReportEntry::new("module::failed", false)
+            .expected("tests/snapshots/failed.txt")
+            .action("verify")
+            .duration(Duration::from_millis(1500))
+            .diff("-\"old\"\n+new\n");
+        assert_eq!(
+            failed.to_json(),
+            r#"{"name":"module::failed","expected":"tests/snapshots/failed.txt","status":"fail","action":"verify","duration":1.5,"diff":"-\"old\"\n+new\n"}"#
+        );
+    }
+
+    #[test]
+    fn entry_junit() {
+        let passed = ReportEntry::new("module::passed", true);
+        assert_eq!(
+            passed.to_junit("testsuite"),
+            r#"<testcase name="module::passed" classname="testsuite"></testcase>"#
+        );
+
+        let failed = ReportEntry::new("module::failed", false)
+            .expected("tests/snapshots/failed.txt")
+            .duration(Duration::from_millis(1500))
+            .diff("-<old>\n+new & improved\n");
+        assert_eq!(
+            failed.to_junit("testsuite"),
+            r#"<testcase name="module::failed" classname="testsuite" time="1.5"><properties><property name="expected" value="tests/snapshots/failed.txt"/></properties><failure message="snapshot mismatch">-&lt;old&gt;
++new &amp; improved
+</failure></testcase>"#
+        );
+    }
+
+    #[test]
+    fn stable_suite_names() {
+        let cases = [
+            ("testsuite-0123456789abcdef", "testsuite"),
+            ("my-tests-fedcba9876543210", "my-tests"),
+            ("my-tests", "my-tests"),
+            ("testsuite-0123456789abcdeg", "testsuite-0123456789abcdeg"),
+            ("-0123456789abcdef", "-0123456789abcdef"),
+        ];
+        for (stem, expected) in cases {
+            assert_eq!(strip_cargo_hash(stem), expected, "{stem}");
+        }
+    }
+
+    #[test]
+    fn merge_suites() {
+        let report = merge_junit("", "first", &ReportEntry::new("a", true));
+        assert_eq!(
+            report,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+<testsuite name="first" tests="1" failures="0" time="0">
+<testcase name="a" classname="first"></testcase>
+</testsuite>
+</testsuites>
+"#
+        );
+        let report = merge_junit(
+            &report,
+            "second",
+            &ReportEntry::new("b", false).diff("-old\n+new\n"),
+        );
+        // Like another test process for the same binary
+        let report = merge_junit(
+            &report,
+            "first",
+            &ReportEntry::new("c", false).duration(Duration::from_millis(500)),
+        );
+        let report = merge_junit(
+            &report,
+            "first",
+            &ReportEntry::new("c", true).duration(Duration::from_millis(250)),
+        );
+        assert_eq!(
+            report,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+<testsuite name="second" tests="1" failures="1" time="0">
+<testcase name="b" classname="second"><failure message="snapshot mismatch">-old
++new
+</failure></testcase>
+</testsuite>
+<testsuite name="first" tests="2" failures="0" time="0.25">
+<testcase name="a" classname="first"></testcase>
+<testcase name="c" classname="first" time="0.25"></testcase>
+</testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[cfg(feature = "dir")]
+    #[test]
+    fn write_junit_across_processes() {
+        let root = crate::dir::DirRoot::mutable_temp().unwrap();
+        let path = root.path().unwrap().join("report.xml");
+        let report = Report::new(&path);
+
+        let threads = (0..8)
+            .map(|i| {
+                let report = report.clone();
+                std::thread::spawn(move || {
+                    // Without the in-process lock, like separate test processes
+                    report.write_junit(&ReportEntry::new(format!("case{i}"), true))
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().unwrap().unwrap();
+        }
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains(r#" tests="8" "#), "{written}");
+        assert!(!path.with_extension("xml.lock").exists());
+
+        root.close().unwrap();
+    }
+}
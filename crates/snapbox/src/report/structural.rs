@@ -0,0 +1,393 @@
+//! Report differences in structured data by path, see [`DiffLayout::Structural`]
+//!
+//! [`DiffLayout::Structural`]: crate::report::DiffLayout::Structural
+
+use serde_json::Value;
+
+const KEY_WILDCARD: &str = "...";
+const VALUE_WILDCARD: &str = "{...}";
+
+/// Above this, array elements are compared by index rather than aligned
+const MAX_ALIGN_CELLS: usize = 1_000_000;
+
+#[derive(Debug, PartialEq)]
+enum Change<'v> {
+    Changed {
+        path: String,
+        expected: &'v Value,
+        actual: &'v Value,
+    },
+    Removed {
+        path: String,
+        expected: &'v Value,
+    },
+    Added {
+        path: String,
+        actual: &'v Value,
+    },
+    Moved {
+        from: String,
+        to: String,
+        value: &'v Value,
+    },
+}
+
+/// Write each difference as a [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901), returning
+/// `false` if there are none to show
+pub(crate) fn write_structural_diff(
+    writer: &mut dyn std::fmt::Write,
+    expected: &Value,
+    actual: &Value,
+    expected_name: Option<&dyn std::fmt::Display>,
+    actual_name: Option<&dyn std::fmt::Display>,
+    palette: crate::report::Palette,
+) -> Result<bool, std::fmt::Error> {
+    let mut changes = Vec::new();
+    diff_value(String::new(), expected, actual, &mut changes);
+    if changes.is_empty() {
+        return Ok(false);
+    }
+
+    super::diff::write_header(writer, expected_name, actual_name, palette)?;
+    for change in changes {
+        match change {
+            Change::Changed {
+                path,
+                expected,
+                actual,
+            } => {
+                writeln!(
+                    writer,
+                    "{} {}: {} → {}",
+                    palette.hint("~"),
+                    display_path(&path),
+                    palette.expected(expected),
+                    palette.actual(actual),
+                )?;
+            }
+            Change::Removed { path, expected } => {
+                writeln!(
+                    writer,
+                    "{}",
+                    palette.error(format_args!("- {}: {expected}", display_path(&path)))
+                )?;
+            }
+            Change::Added { path, actual } => {
+                writeln!(
+                    writer,
+                    "{}",
+                    palette.info(format_args!("+ {}: {actual}", display_path(&path)))
+                )?;
+            }
+            Change::Moved { from, to, value } => {
+                writeln!(
+                    writer,
+                    "{} {} → {}: {}",
+                    palette.hint("↕"),
+                    display_path(&from),
+                    display_path(&to),
+                    palette.hint(value),
+                )?;
+            }
+        }
+    }
+    Ok(true)
+}
+
+fn diff_value<'v>(
+    path: String,
+    expected: &'v Value,
+    actual: &'v Value,
+    changes: &mut Vec<Change<'v>>,
+) {
+    match (expected, actual) {
+        (Value::String(exp), _) if exp == VALUE_WILDCARD => {}
+        (Value::Object(exp), Value::Object(act)) => {
+            let has_key_wildcard =
+                exp.get(KEY_WILDCARD).and_then(|v| v.as_str()) == Some(VALUE_WILDCARD);
+            let mut keys = exp.keys().collect::<Vec<_>>();
+            keys.sort();
+            for key in keys {
+                let expected = &exp[key];
+                let key_path = format!("{path}/{}", escape_key(key));
+                if has_key_wildcard && key == KEY_WILDCARD {
+                    continue;
+                }
+                match act.get(key) {
+                    Some(actual) => diff_value(key_path, expected, actual, changes),
+                    None => changes.push(Change::Removed {
+                        path: key_path,
+                        expected,
+                    }),
+                }
+            }
+            if !has_key_wildcard {
+                let mut keys = act
+                    .keys()
+                    .filter(|key| !exp.contains_key(*key))
+                    .collect::<Vec<_>>();
+                keys.sort();
+                for key in keys {
+                    changes.push(Change::Added {
+                        path: format!("{path}/{}", escape_key(key)),
+                        actual: &act[key],
+                    });
+                }
+            }
+        }
+        (Value::Array(exp), Value::Array(act)) => diff_array(&path, exp, act, changes),
+        (exp, act) => {
+            if exp != act {
+                changes.push(Change::Changed {
+                    path,
+                    expected: exp,
+                    actual: act,
+                });
+            }
+        }
+    }
+}
+
+/// Align matching elements, reporting the rest by where they are in `expected` or `actual`
+fn diff_array<'v>(
+    path: &str,
+    expected: &'v [Value],
+    actual: &'v [Value],
+    changes: &mut Vec<Change<'v>>,
+) {
+    let matches = align(expected, actual);
+
+    // Elements not in `matches`, between consecutive matches
+    let mut gaps = Vec::new();
+    let (mut expected_start, mut actual_start) = (0, 0);
+    for (expected_end, actual_end) in matches
+        .iter()
+        .copied()
+        .chain(std::iter::once((expected.len(), actual.len())))
+    {
+        gaps.push((expected_start..expected_end, actual_start..actual_end));
+        expected_start = expected_end + 1;
+        actual_start = actual_end + 1;
+    }
+
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for (expected_range, actual_range) in &gaps {
+        let has_wildcard = expected[expected_range.clone()]
+            .iter()
+            .any(|value| value == VALUE_WILDCARD);
+        if has_wildcard {
+            // `{...}` stands in for any number of elements
+            removed.extend(
+                expected_range
+                    .clone()
+                    .filter(|i| expected[*i] != VALUE_WILDCARD),
+            );
+        } else {
+            removed.extend(expected_range.clone());
+            added.extend(actual_range.clone());
+        }
+    }
+
+    let mut moved = Vec::new();
+    removed.retain(|&expected_index| {
+        let Some(position) = added
+            .iter()
+            .position(|&actual_index| expected[expected_index] == actual[actual_index])
+        else {
+            return true;
+        };
+        moved.push((expected_index, added.remove(position)));
+        false
+    });
+
+    for (expected_range, actual_range) in gaps {
+        let expected_indices = expected_range
+            .filter(|i| removed.contains(i))
+            .collect::<Vec<_>>();
+        let actual_indices = actual_range
+            .filter(|i| added.contains(i))
+            .collect::<Vec<_>>();
+        let paired = expected_indices.len().min(actual_indices.len());
+        for (&expected_index, &actual_index) in expected_indices.iter().zip(&actual_indices) {
+            diff_value(
+                format!("{path}/{expected_index}"),
+                &expected[expected_index],
+                &actual[actual_index],
+                changes,
+            );
+        }
+        for &expected_index in &expected_indices[paired..] {
+            changes.push(Change::Removed {
+                path: format!("{path}/{expected_index}"),
+                expected: &expected[expected_index],
+            });
+        }
+        for &actual_index in &actual_indices[paired..] {
+            changes.push(Change::Added {
+                path: format!("{path}/{actual_index}"),
+                actual: &actual[actual_index],
+            });
+        }
+    }
+    for (expected_index, actual_index) in moved {
+        changes.push(Change::Moved {
+            from: format!("{path}/{expected_index}"),
+            to: format!("{path}/{actual_index}"),
+            value: &expected[expected_index],
+        });
+    }
+}
+
+/// Indices of equal elements, in order, through the longest common subsequence
+fn align(expected: &[Value], actual: &[Value]) -> Vec<(usize, usize)> {
+    // Wildcards are resolved through the gaps around them
+    let is_match = |e: &Value, a: &Value| e != VALUE_WILDCARD && e == a;
+
+    if MAX_ALIGN_CELLS < (expected.len() + 1) * (actual.len() + 1) {
+        return expected
+            .iter()
+            .zip(actual)
+            .enumerate()
+            .filter(|(_, (e, a))| is_match(e, a))
+            .map(|(i, _)| (i, i))
+            .collect();
+    }
+
+    // `lengths[i][j]` is the length of the common subsequence of `expected[i..]` and `actual[j..]`
+    let width = actual.len() + 1;
+    let mut lengths = vec![0usize; (expected.len() + 1) * width];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lengths[i * width + j] = if is_match(&expected[i], &actual[j]) {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < actual.len() {
+        if is_match(&expected[i], &actual[j]) {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+fn escape_key(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() { "(root)" } else { path }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn diff(expected: Value, actual: Value) -> String {
+        let mut buf = String::new();
+        write_structural_diff(
+            &mut buf,
+            &expected,
+            &actual,
+            None,
+            None,
+            crate::report::Palette::plain(),
+        )
+        .unwrap();
+        buf
+    }
+
+    #[test]
+    fn nested_changes() {
+        let expected = json!({
+            "name": "snapbox",
+            "items": [{"id": 1, "name": "a"}, {"id": 2, "name": "b"}],
+            "removed": true,
+            "a/b~c": 1,
+        });
+        let actual = json!({
+            "name": "snapbox",
+            "items": [{"id": 1, "name": "a"}, {"id": 2, "name": "c"}],
+            "added": [1],
+            "a/b~c": "1",
+        });
+        assert_eq!(
+            diff(expected, actual),
+            r#"
+--- Expected
++++ Actual
+~ /a~1b~0c: 1 → "1"
+~ /items/1/name: "b" → "c"
+- /removed: true
++ /added: [1]
+"#
+        );
+    }
+
+    #[test]
+    fn array_changes() {
+        let expected = json!(["a", "b", "c", "d", {"id": 5}]);
+        let actual = json!(["b", "c", "a", "x", {"id": 6}, "e"]);
+        assert_eq!(
+            diff(expected, actual),
+            r#"
+--- Expected
++++ Actual
+~ /3: "d" → "x"
+~ /4/id: 5 → 6
++ /5: "e"
+↕ /0 → /2: "a"
+"#
+        );
+    }
+
+    #[test]
+    fn wildcards() {
+        let expected = json!({
+            "id": "{...}",
+            "items": ["first", "{...}", "last"],
+            "nested": {"kept": 1, "...": "{...}"},
+        });
+        let actual = json!({
+            "id": 42,
+            "items": ["first", "one", "two", "changed"],
+            "nested": {"kept": 2, "extra": true},
+        });
+        assert_eq!(
+            diff(expected, actual),
+            r#"
+--- Expected
++++ Actual
+- /items/2: "last"
+~ /nested/kept: 1 → 2
+"#
+        );
+    }
+
+    #[test]
+    fn root_and_equal() {
+        assert_eq!(
+            diff(json!(1), json!(2)),
+            "
+--- Expected
++++ Actual
+~ (root): 1 → 2
+"
+        );
+        assert_eq!(diff(json!({"a": [1, 2]}), json!({"a": [1, 2]})), "");
+    }
+}
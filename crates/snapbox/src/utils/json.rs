@@ -0,0 +1,126 @@
+//! Minimal JSON for snapbox's own files, which must work without the `json` feature
+
+/// Append `value` as a JSON string
+pub(crate) fn encode_json_str(buf: &mut String, value: &str) {
+    buf.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if c < ' ' => buf.push_str(&format!("\\u{:04x}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+/// Read back the strings and integers written with [`encode_json_str`]
+pub(crate) struct JsonParser<'s> {
+    rest: &'s str,
+}
+
+impl<'s> JsonParser<'s> {
+    pub(crate) fn new(text: &'s str) -> Self {
+        Self { rest: text }
+    }
+
+    /// Whether only whitespace is left
+    pub(crate) fn is_done(&self) -> bool {
+        self.rest.trim().is_empty()
+    }
+
+    /// Consume `c`, skipping whitespace before it
+    pub(crate) fn eat(&mut self, c: char) -> Option<()> {
+        self.rest = self.rest.trim_start().strip_prefix(c)?;
+        Some(())
+    }
+
+    pub(crate) fn number(&mut self) -> Option<u32> {
+        self.rest = self.rest.trim_start();
+        let len = self
+            .rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len());
+        let (number, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        number.parse().ok()
+    }
+
+    pub(crate) fn str(&mut self) -> Option<String> {
+        self.eat('"')?;
+        let mut value = String::new();
+        let mut chars = self.rest.chars();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => {
+                    let c = match chars.next()? {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'u' => {
+                            let hex = chars.as_str().get(..4)?;
+                            let c = char::from_u32(u32::from_str_radix(hex, 16).ok()?)?;
+                            chars = chars.as_str()[4..].chars();
+                            c
+                        }
+                        c => c,
+                    };
+                    value.push(c);
+                }
+                c => value.push(c),
+            }
+        }
+        self.rest = chars.as_str();
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn str_round_trip() {
+        let cases = [
+            ("", r#""""#),
+            ("plain", r#""plain""#),
+            ("quote \" slash \\", r#""quote \" slash \\""#),
+            ("new\nline\r\ttab", r#""new\nline\r\ttab""#),
+            ("\u{1b}[0m", r#""\u001b[0m""#),
+            ("unicode ✓", r#""unicode ✓""#),
+        ];
+        for (value, expected) in cases {
+            let mut encoded = String::new();
+            encode_json_str(&mut encoded, value);
+            assert_eq!(encoded, expected, "{value:?}");
+            let mut parser = JsonParser::new(&encoded);
+            assert_eq!(parser.str().as_deref(), Some(value), "{value:?}");
+            assert!(parser.is_done(), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let mut parser = JsonParser::new(r#" { "a" : 12 , "b":"é\/" } "#);
+        assert_eq!(parser.eat('{'), Some(()));
+        assert_eq!(parser.str().as_deref(), Some("a"));
+        assert_eq!(parser.eat(':'), Some(()));
+        assert_eq!(parser.number(), Some(12));
+        assert_eq!(parser.eat(','), Some(()));
+        assert_eq!(parser.str().as_deref(), Some("b"));
+        assert_eq!(parser.eat(':'), Some(()));
+        assert_eq!(parser.str().as_deref(), Some("é/"));
+        assert_eq!(parser.eat('}'), Some(()));
+        assert!(parser.is_done());
+
+        assert_eq!(JsonParser::new("12").str(), None);
+        assert_eq!(JsonParser::new(r#""unterminated"#).str(), None);
+        assert_eq!(JsonParser::new("x").number(), None);
+    }
+}
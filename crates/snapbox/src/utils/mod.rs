@@ -1,5 +1,8 @@
+mod json;
 mod lines;
 
+pub(crate) use json::JsonParser;
+pub(crate) use json::encode_json_str;
 pub use lines::LinesWithTerminator;
 
 #[doc(inline)]
@@ -2,6 +2,7 @@ use snapbox::assert_data_eq;
 use snapbox::data::IntoData;
 use snapbox::file;
 use snapbox::str;
+use snapbox::Assert;
 
 #[test]
 fn test_trivial_assert() {
@@ -53,3 +54,109 @@ fn actual_expected_formats_differ() {
 "#]].is_json().against_jsonlines(),
     );
 }
+
+#[test]
+fn whitespace_insensitive() {
+    let actual = "\
+name     version  
+snapbox  1.0      
+
+
+trycmd   2.0      
+";
+    assert_data_eq!(
+        actual,
+        str![[r#"
+name version
+snapbox 1.0
+trycmd 2.0
+
+"#]]
+        .collapse_spaces()
+        .trim_trailing_whitespace()
+        .remove_blank_lines()
+    );
+}
+
+#[test]
+fn soft_collects_failures() {
+    let result = std::panic::catch_unwind(|| {
+        Assert::new().soft(|assert| {
+            assert.eq("one", "1");
+            assert.eq("two", "two");
+            assert_data_eq!("three", "3");
+            "done"
+        })
+    });
+    let err = result.unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("2 snapshot assertion(s) failed\n"), "{message}");
+    assert!(message.contains("[1/2] crates/snapbox/tests/testsuite/assert.rs:"), "{message}");
+    assert!(message.contains("[2/2] crates/snapbox/tests/testsuite/assert.rs:"), "{message}");
+    assert!(message.contains("one"), "{message}");
+    assert!(!message.contains("two"), "{message}");
+    assert!(message.contains("three"), "{message}");
+}
+
+#[test]
+fn soft_passes() {
+    let value = Assert::new().soft(|assert| {
+        assert.eq("one", "one");
+        1
+    });
+    assert_eq!(value, 1);
+}
+
+#[test]
+#[cfg(feature = "dir")]
+fn report_names_each_comparison() {
+    let root = snapbox::dir::DirRoot::mutable_temp().unwrap();
+    let path = root.path().unwrap().join("report.jsonl");
+    let assert = Assert::new()
+        .action(snapbox::assert::Action::Verify)
+        .palette(snapbox::report::Palette::color())
+        .report(snapbox::report::Report::new(&path));
+
+    assert
+        .try_eq(Some(&"stdout"), "one".into_data(), str!["one"].into_data())
+        .unwrap();
+    assert
+        .try_eq(Some(&"stderr"), "two".into_data(), str!["one"].into_data())
+        .unwrap_err();
+
+    let report = std::fs::read_to_string(&path).unwrap();
+    let lines = report.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2, "{report}");
+    assert!(
+        lines[0].contains(r#""name":"assert::report_names_each_comparison stdout (tests/testsuite/assert.rs:"#),
+        "{report}"
+    );
+    assert!(
+        lines[1].contains(r#""name":"assert::report_names_each_comparison stderr (tests/testsuite/assert.rs:"#),
+        "{report}"
+    );
+    assert!(lines[1].contains(r#""status":"fail""#), "{report}");
+    assert!(!report.contains("\\u001b"), "{report}");
+}
+
+#[test]
+#[cfg(all(feature = "toml", feature = "dir"))]
+fn overwrite_toml_keeps_datetimes() {
+    let root = snapbox::dir::DirRoot::mutable_temp().unwrap();
+    let path = root.path().unwrap().join("snapshot.toml");
+    std::fs::write(&path, "released = 2000-01-01T00:00:00Z\n").unwrap();
+    let expected = || snapbox::Data::read_from(&path, Some(snapbox::data::DataFormat::Toml));
+    let actual = "name = \"snapbox\"\nreleased = 1979-05-27T07:32:00Z\n";
+
+    Assert::new()
+        .action(snapbox::assert::Action::Overwrite)
+        .try_eq(None, actual.into_data(), expected())
+        .unwrap();
+    let written = std::fs::read_to_string(&path).unwrap();
+    Assert::new()
+        .action(snapbox::assert::Action::Verify)
+        .try_eq(None, actual.into_data(), expected())
+        .unwrap();
+
+    assert_eq!(written, actual);
+}
@@ -41,3 +41,19 @@ help: available binary names are \"snap-fixture\""]
 fn cargo_bin_non_existent() {
     let _ = snapbox::cmd::cargo_bin("non-existent");
 }
+
+#[test]
+fn soft_collects_exit_status() {
+    let result = std::panic::catch_unwind(|| {
+        snapbox::Assert::new().soft(|_| {
+            snapbox::cmd::Command::new(snapbox::cmd::cargo_bin!("snap-fixture"))
+                .env("exit", "1")
+                .assert()
+                .success()
+                .code(2);
+        });
+    });
+    let err = result.unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("2 snapshot assertion(s) failed\n"), "{message}");
+}
@@ -6,6 +6,7 @@ pub struct TestCases {
     runner: std::cell::RefCell<crate::RunnerSpec>,
     bins: std::cell::RefCell<crate::BinRegistry>,
     substitutions: std::cell::RefCell<snapbox::Redactions>,
+    report: std::cell::RefCell<Option<snapbox::report::Report>>,
     has_run: std::cell::Cell<bool>,
 }
 
@@ -106,6 +107,14 @@ impl TestCases {
         self
     }
 
+    /// Record each case in a machine-readable [`Report`][snapbox::report::Report]
+    ///
+    /// The default is to report to the path in `TRYCMD_REPORT`, if set.
+    pub fn report(&self, report: snapbox::report::Report) -> &Self {
+        self.report.replace(Some(report));
+        self
+    }
+
     /// Add a variable for normalizing output
     ///
     /// Variable names must be
@@ -175,11 +184,25 @@ impl TestCases {
     pub fn run(&self) {
         self.has_run.set(true);
 
-        let mode = parse_mode(std::env::var_os("TRYCMD").as_deref());
+        let mode = parse_mode(
+            std::env::var_os("TRYCMD").as_deref(),
+            snapbox::assert::is_overwrite_allowed(),
+        );
         mode.initialize().unwrap();
 
+        let report = self
+            .report
+            .borrow()
+            .clone()
+            .or_else(|| snapbox::report::Report::with_env_var("TRYCMD_REPORT"));
+
         let runner = self.runner.borrow_mut().prepare();
-        runner.run(&mode, &self.bins.borrow(), &self.substitutions.borrow());
+        runner.run(
+            &mode,
+            &self.bins.borrow(),
+            &self.substitutions.borrow(),
+            report.as_ref(),
+        );
     }
 }
 
@@ -229,9 +252,20 @@ fn parse_include(args: impl IntoIterator<Item = std::ffi::OsString>) -> Option<V
     }
 }
 
-fn parse_mode(var: Option<&std::ffi::OsStr>) -> crate::Mode {
+fn parse_mode(var: Option<&std::ffi::OsStr>, overwrite_allowed: bool) -> crate::Mode {
     if var == Some(std::ffi::OsStr::new("overwrite")) {
-        crate::Mode::Overwrite
+        if overwrite_allowed {
+            crate::Mode::Overwrite
+        } else {
+            use std::io::Write as _;
+
+            let _ = writeln!(
+                std::io::stderr(),
+                "Refusing `TRYCMD=overwrite` in CI, set `{}=1` to allow it",
+                snapbox::assert::ALLOW_CI_OVERWRITE_ENV
+            );
+            crate::Mode::Fail
+        }
     } else if var == Some(std::ffi::OsStr::new("dump")) {
         crate::Mode::Dump("dump".into())
     } else {
@@ -61,7 +61,9 @@
 //! ```console
 //! $ TRYCMD=overwrite cargo test --test cli_tests
 //! ```
-//! This will overwrite any existing `.stdout` and `.stderr` file in `tests/cmd`
+//! This will overwrite any existing `.stdout` and `.stderr` file in `tests/cmd`.
+//! When running in CI, this is refused unless `SNAPSHOTS_ALLOW_CI_OVERWRITE=1` is set, see
+//! [`snapbox::assert::is_overwrite_allowed`].
 //!
 //! To filter the tests to those with `name1`, `name2`, etc in their file names, you can run:
 //! ```console
@@ -70,6 +72,12 @@
 //!
 //! To debug what `trycmd` is doing, run `cargo test -F trycmd/debug`.
 //!
+//! To record each case for CI, run
+//! ```console
+//! $ TRYCMD_REPORT=target/trycmd.xml cargo test --test cli_tests
+//! ```
+//! This will write `JUnit` XML, or JSON Lines for other extensions, see [`snapbox::report::Report`].
+//!
 //! ## File Formats
 //!
 //! For `tests/cmd/help.trycmd`, `trycmd` will look for:
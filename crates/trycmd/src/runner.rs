@@ -38,6 +38,7 @@ impl Runner {
         mode: &Mode,
         bins: &crate::BinRegistry,
         substitutions: &snapbox::Redactions,
+        report: Option<&snapbox::report::Report>,
     ) {
         #![allow(unexpected_cfgs)] // HACK: until we upgrade the minimum anstream
         let palette = snapbox::report::Palette::color();
@@ -58,6 +59,20 @@ impl Runner {
                         .into_iter()
                         .filter_map(|s| {
                             snapbox::debug!("Case: {:#?}", s);
+                            if let Some(report) = report {
+                                let entry = match &s {
+                                    Ok(status) => status.report_entry(true, mode),
+                                    Err(status) => status.report_entry(false, mode),
+                                };
+                                if let Err(err) = report.write(&entry) {
+                                    let _ = writeln!(
+                                        stderr,
+                                        "{}: {}",
+                                        palette.warn("Report failed"),
+                                        err
+                                    );
+                                }
+                            }
                             match s {
                                 Ok(status) => {
                                     let _ = write!(
@@ -644,6 +659,20 @@ impl Output {
             && self.fs.is_ok()
     }
 
+    fn report_entry(&self, passed: bool, mode: &Mode) -> snapbox::report::ReportEntry {
+        let mut entry =
+            snapbox::report::ReportEntry::new(self.name(), passed).expected(self.path.display());
+        if let Some(duration) = self.duration {
+            entry = entry.duration(duration);
+        }
+        if !self.is_ok() {
+            entry = entry
+                .diff(snapbox::filter::strip_ansi(&self.to_string()))
+                .action(mode.as_str());
+        }
+        entry
+    }
+
     fn name(&self) -> String {
         self.id
             .as_deref()
@@ -1035,6 +1064,14 @@ pub(crate) enum Mode {
 }
 
 impl Mode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fail => "fail",
+            Self::Overwrite => "overwrite",
+            Self::Dump(_) => "dump",
+        }
+    }
+
     pub(crate) fn initialize(&self) -> Result<(), std::io::Error> {
         match self {
             Self::Fail => {}